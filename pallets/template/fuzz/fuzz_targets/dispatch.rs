@@ -0,0 +1,324 @@
+//! Fuzz random sequences of template pallet calls against the pallet's mock runtime.
+//!
+//! Each input is a list of [`Step`]s: calls to either pallet instance from a random origin, new
+//! blocks and `on_idle` runs. After every step the storage invariants of both instances are
+//! checked, and the storage proof the step needed is compared to the proof size of the weight it
+//! declared. For dispatched calls, the storage items read and written are also counted against
+//! the reads and writes the call's weight charges through `DbWeight`. A panic anywhere is reported
+//! as a crash.
+//!
+//! The `ref_time` of weights is not covered: execution time can't be measured reliably under the
+//! fuzzer's instrumentation, so only the storage accesses that make up part of it are checked.
+//! Benchmarks remain the only check on the rest.
+//!
+//! Run it from `pallets/template` with `cargo fuzz run dispatch`. It needs no network access
+//! once its dependencies have been fetched.
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use codec::Encode;
+use frame_support::{
+	dispatch::GetDispatchInfo,
+	instances::Instance2,
+	sp_io,
+	storage::storage_prefix,
+	traits::Hooks,
+	weights::{RuntimeDbWeight, Weight},
+	BoundedVec,
+};
+use libfuzzer_sys::fuzz_target;
+use pallet_template::{mock::*, BatchMode, ValuePayload};
+use sp_runtime::{
+	testing::{TestSignature, UintAuthorityId, H256},
+	traits::{BlakeTwo256, Dispatchable},
+};
+use sp_state_machine::{Backend, StorageProof};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The most steps run for a single input.
+const MAX_STEPS: usize = 64;
+
+/// The proof size allowed on top of a step's declared weight, for storage that benchmarks
+/// whitelist instead of charging it to each call, like the block number and events.
+const UNMETERED_PROOF_SIZE: u64 = 2 * 1024;
+
+/// The storage of the mock runtime, by key.
+type Storage = BTreeMap<Vec<u8>, Vec<u8>>;
+
+/// The accounts that calls are made by and refer to, including ones without funds.
+const ACCOUNTS: [u64; 8] = [1, 2, 3, 4, 5, 6, 100, 1000];
+
+fn account(index: u8) -> u64 {
+	ACCOUNTS[index as usize % ACCOUNTS.len()]
+}
+
+#[derive(Debug, Arbitrary)]
+enum Origin {
+	Root,
+	None,
+	Signed(u8),
+}
+
+impl From<Origin> for RuntimeOrigin {
+	fn from(origin: Origin) -> Self {
+		match origin {
+			Origin::Root => RuntimeOrigin::root(),
+			Origin::None => RuntimeOrigin::none(),
+			Origin::Signed(who) => RuntimeOrigin::signed(account(who)),
+		}
+	}
+}
+
+/// A [`pallet_template::Call`] with arguments drawn from small ranges, so that calls in a
+/// sequence refer to the same accounts, blocks, counters and commitments.
+#[derive(Debug, Arbitrary)]
+enum TemplateCall {
+	DoSomething { value: u32 },
+	CauseError,
+	ClearSomething,
+	AddWriter { who: u8 },
+	RemoveWriter { who: u8 },
+	ScheduleSet { value: u32, in_blocks: u8 },
+	CancelScheduled { in_blocks: u8 },
+	SubmitValueUnsigned { key: u8, value: u32 },
+	CreateCounter { name: u8, value: u64, min: Option<u64>, max: Option<u64> },
+	Increment { name: u8, by: u64 },
+	Decrement { name: u8, by: u64 },
+	Reset { name: u8 },
+	TransferOwnership { name: u8, new_owner: u8 },
+	Commit { value: u8, salt: u8 },
+	Reveal { value: u8, salt: u8 },
+	AddFeeder { who: u8 },
+	RemoveFeeder { who: u8 },
+	Feed { value: u32 },
+	DoSomethingWithTtl { value: u32, ttl: u8 },
+	SetMany { values: Vec<(u8, u32)>, atomic: bool },
+	Propose { value: u32 },
+	Vote { index: u8, aye: bool, balance: u64 },
+	DeleteCounter { name: u8 },
+}
+
+impl TemplateCall {
+	fn into_call<I: 'static>(self) -> pallet_template::Call<Test, I>
+	where
+		Test: pallet_template::Config<I>,
+	{
+		use pallet_template::Call;
+
+		let now = System::block_number();
+		let name = |name: u8| BoundedVec::truncate_from(vec![b'a' + name % 4]);
+		match self {
+			Self::DoSomething { value } => Call::do_something { something: value },
+			Self::CauseError => Call::cause_error {},
+			Self::ClearSomething => Call::clear_something {},
+			Self::AddWriter { who } => Call::add_writer { who: account(who) },
+			Self::RemoveWriter { who } => Call::remove_writer { who: account(who) },
+			Self::ScheduleSet { value, in_blocks } =>
+				Call::schedule_set { value, at: now + u64::from(in_blocks) },
+			Self::CancelScheduled { in_blocks } =>
+				Call::cancel_scheduled { at: now + u64::from(in_blocks) },
+			Self::SubmitValueUnsigned { key, value } => {
+				let key = account(key);
+				let payload =
+					ValuePayload { block_number: now, public: UintAuthorityId(key), value };
+				let signature = TestSignature(key, payload.encode());
+				Call::submit_value_unsigned { payload, signature }
+			},
+			Self::CreateCounter { name: n, value, min, max } =>
+				Call::create_counter { name: name(n), value, min, max },
+			Self::Increment { name: n, by } => Call::increment { name: name(n), by },
+			Self::Decrement { name: n, by } => Call::decrement { name: name(n), by },
+			Self::Reset { name: n } => Call::reset { name: name(n) },
+			Self::TransferOwnership { name: n, new_owner } =>
+				Call::transfer_ownership { name: name(n), new_owner: account(new_owner) },
+			Self::Commit { value, salt } => Call::commit {
+				hash: pallet_template::Pallet::<Test, I>::commitment_hash(
+					value.into(),
+					&[salt; 32],
+				),
+			},
+			Self::Reveal { value, salt } => Call::reveal { value: value.into(), salt: [salt; 32] },
+			Self::AddFeeder { who } => Call::add_feeder { who: account(who) },
+			Self::RemoveFeeder { who } => Call::remove_feeder { who: account(who) },
+			Self::Feed { value } => Call::feed { value },
+			Self::DoSomethingWithTtl { value, ttl } =>
+				Call::do_something_with_ttl { something: value, ttl: ttl.into() },
+			Self::SetMany { values, atomic } => Call::set_many {
+				values: BoundedVec::truncate_from(
+					values.into_iter().map(|(who, value)| (account(who), value)).collect(),
+				),
+				mode: if atomic { BatchMode::Atomic } else { BatchMode::BestEffort },
+			},
+			Self::Propose { value } => Call::propose { value },
+			Self::Vote { index, aye, balance } =>
+				Call::vote { index: (index % 8).into(), aye, balance },
+			Self::DeleteCounter { name: n } => Call::delete_counter { name: name(n) },
+		}
+	}
+}
+
+#[derive(Debug, Arbitrary)]
+enum Step {
+	/// Dispatch a call to the default instance, or to the second one if `second` is set.
+	Dispatch { second: bool, origin: Origin, call: TemplateCall },
+	/// Finalize the current block and initialize the next one.
+	NextBlock,
+	/// Run `on_idle` with the given weight left in the block.
+	Idle { ref_time: u16, proof_size: u16 },
+	/// Change the origins the default instance's `SetOrigin` accepts.
+	SetOrigin(u8),
+}
+
+impl Step {
+	/// Run the step, returning the weight it declared and, for a dispatched call, the storage
+	/// reads and writes that weight charges.
+	///
+	/// `initialized` is the weight `on_initialize` reserved for the current block, which also
+	/// covers its `on_finalize`.
+	fn run(self, initialized: &mut Weight) -> (Weight, Option<(u64, u64)>) {
+		match self {
+			Self::Dispatch { second, origin, call } => {
+				let call = if second {
+					RuntimeCall::SecondTemplate(call.into_call::<Instance2>())
+				} else {
+					RuntimeCall::TemplateModule(call.into_call::<()>())
+				};
+				let declared = call.get_dispatch_info().weight;
+				let accesses = declared_accesses(&call);
+				// Failing calls are expected, they must just not panic or break invariants.
+				let _ = call.dispatch(origin.into());
+				(declared, Some(accesses))
+			},
+			Self::NextBlock => {
+				let now = System::block_number();
+				TemplateModule::on_finalize(now);
+				SecondTemplate::on_finalize(now);
+				let finalized = *initialized;
+				*initialized = initialize_block(now + 1);
+				(finalized.saturating_add(*initialized), None)
+			},
+			Self::Idle { ref_time, proof_size } => {
+				let limit = Weight::from_parts(
+					u64::from(ref_time) * 10_000_000,
+					u64::from(proof_size) * 16,
+				);
+				let now = System::block_number();
+				let used = TemplateModule::on_idle(now, limit);
+				let used =
+					used.saturating_add(SecondTemplate::on_idle(now, limit.saturating_sub(used)));
+				assert!(used.all_lte(limit), "on_idle used {:?} of {:?}", used, limit);
+				(used, None)
+			},
+			Self::SetOrigin(kind) => {
+				SetOriginAccepts::set(match kind % 3 {
+					0 => SetOriginKind::AnySigned,
+					1 => SetOriginKind::Allowlist,
+					_ => SetOriginKind::Root,
+				});
+				(Weight::zero(), None)
+			},
+		}
+	}
+}
+
+/// The storage reads and writes the weight of `call` charges, found by weighing it with each
+/// access costing a single unit of `ref_time` and with accesses being free.
+fn declared_accesses(call: &RuntimeCall) -> (u64, u64) {
+	let weigh = |read, write| {
+		MockDbWeight::set(RuntimeDbWeight { read, write });
+		call.get_dispatch_info().weight.ref_time()
+	};
+	let free = weigh(0, 0);
+	let accesses = (weigh(1, 0) - free, weigh(0, 1) - free);
+	MockDbWeight::set(RuntimeDbWeight { read: 0, write: 0 });
+	accesses
+}
+
+/// Every key and value in storage.
+fn storage() -> Storage {
+	let mut storage = Storage::new();
+	let mut key = Vec::new();
+	while let Some(next) = sp_io::storage::next_key(&key) {
+		let value = sp_io::storage::get(&next).expect("`next_key` only returns set keys");
+		storage.insert(next.clone(), value.to_vec());
+		key = next;
+	}
+	storage
+}
+
+/// Whether benchmarks leave accesses to `key` out of the weight, as they do for the block
+/// number, events and the rest of `frame_system`'s storage apart from accounts.
+fn is_whitelisted(key: &[u8]) -> bool {
+	let system = storage_prefix(b"System", b"Account");
+	key.starts_with(&system[..16]) && !key.starts_with(&system)
+}
+
+/// The storage items in `before` that a step read to produce `proof`, and the ones that differ
+/// between `before` and `after`, leaving out whitelisted items.
+///
+/// An item counts as read if its value can be looked up in the proof, and as written only if
+/// the step changed it, so both counts are at most what the step accessed.
+fn accesses(before: &Storage, after: &Storage, root: H256, proof: StorageProof) -> (u64, u64) {
+	let proved = sp_state_machine::create_proof_check_backend::<BlakeTwo256>(root, proof)
+		.expect("the proof was recorded against this root");
+	let reads = before
+		.keys()
+		.filter(|key| !is_whitelisted(key))
+		.filter(|key| matches!(proved.storage(key), Ok(Some(_))))
+		.count();
+	let writes = before
+		.keys()
+		.chain(after.keys())
+		.collect::<BTreeSet<_>>()
+		.into_iter()
+		.filter(|key| !is_whitelisted(key) && before.get(*key) != after.get(*key))
+		.count();
+	(reads as u64, writes as u64)
+}
+
+/// Start block `n` and run both instances' `on_initialize`, returning the weight they reserved.
+fn initialize_block(n: u64) -> Weight {
+	System::set_block_number(n);
+	Timestamp::set_timestamp(n * BLOCK_TIME);
+	TemplateModule::on_initialize(n).saturating_add(SecondTemplate::on_initialize(n))
+}
+
+fuzz_target!(|steps: Vec<Step>| {
+	SetOriginAccepts::set(SetOriginKind::AnySigned);
+	let mut ext = new_test_ext();
+	let mut initialized = ext.execute_with(|| initialize_block(1));
+
+	for step in steps.into_iter().take(MAX_STEPS) {
+		// Start from a committed state, so that the proof holds everything the step reads.
+		ext.execute_with(System::reset_events);
+		ext.commit_all().expect("the test backend accepts all changes");
+		let root = *ext.backend.root();
+		let before = ext.execute_with(storage);
+		let description = format!("{:?}", step);
+		let ((declared, charged), proof) = ext.execute_and_prove(|| step.run(&mut initialized));
+		let proof_size = proof.encoded_size() as u64;
+		assert!(
+			proof_size <= declared.proof_size() + UNMETERED_PROOF_SIZE,
+			"{} read a {} byte proof, but declared {:?}",
+			description,
+			proof_size,
+			declared,
+		);
+		if let Some((declared_reads, declared_writes)) = charged {
+			let after = ext.execute_with(storage);
+			let (reads, writes) = accesses(&before, &after, root, proof);
+			assert!(
+				reads <= declared_reads && writes <= declared_writes,
+				"{} read {} and wrote {} storage items, but declared {} reads and {} writes",
+				description,
+				reads,
+				writes,
+				declared_reads,
+				declared_writes,
+			);
+		}
+		// Check the invariants after the step.
+		ext.execute_with(|| ());
+	}
+});
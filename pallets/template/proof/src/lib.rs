@@ -0,0 +1,55 @@
+//! Verification of storage proofs of the values stored by the template pallet.
+//!
+//! A block's header commits to all of the chain's state through its state root, so the pallet
+//! keeps no checkpoints of its own: a proof of what `Something` held for an account at a block is
+//! a storage read proof of the value's key against that block's state root. Nodes export these
+//! proofs through the `template_getValueProof` RPC, and [`verify_value_proof`] checks one against
+//! a header, e.g. in a light client or in another chain's runtime.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{DecodeAll, Encode};
+use sp_core::hashing::{blake2_128, twox_128};
+use sp_runtime::traits::{Hash, Header};
+use sp_std::vec::Vec;
+use sp_trie::{read_trie_value, LayoutV1, StorageProof};
+
+/// Why a value proof was rejected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Error {
+	/// The proof lacks the trie nodes from the state root to the key.
+	InvalidProof,
+	/// The proven value is not a `u32`.
+	InvalidValue,
+}
+
+/// The storage key of the value of `who` in the `Something` map of the pallet instance named
+/// `pallet` in the runtime, the same key the runtime's `TemplateProofApi::value_key` returns.
+pub fn value_key(pallet: &str, who: &impl Encode) -> Vec<u8> {
+	let who = who.encode();
+	[&twox_128(pallet.as_bytes())[..], &twox_128(b"Something"), &blake2_128(&who), &who].concat()
+}
+
+/// Check `proof` of the value under `key` against the state root of `header`.
+///
+/// Returns the proven value, or `None` if the proof shows that no value was stored under `key`.
+pub fn verify_value_proof<H: Header>(
+	header: &H,
+	key: &[u8],
+	proof: impl IntoIterator<Item = Vec<u8>>,
+) -> Result<Option<u32>, Error> {
+	verify_value_proof_at::<H::Hashing>(header.state_root(), key, proof)
+}
+
+/// Check `proof` of the value under `key` against the state root `root`.
+pub fn verify_value_proof_at<H: Hash>(
+	root: &H::Output,
+	key: &[u8],
+	proof: impl IntoIterator<Item = Vec<u8>>,
+) -> Result<Option<u32>, Error> {
+	let db = StorageProof::new(proof).into_memory_db::<H>();
+	read_trie_value::<LayoutV1<H>, _>(&db, root, key, None, None)
+		.map_err(|_| Error::InvalidProof)?
+		.map(|value| u32::decode_all(&mut &value[..]).map_err(|_| Error::InvalidValue))
+		.transpose()
+}
@@ -0,0 +1,179 @@
+//! RPC interface for the template pallet.
+//!
+//! Exposes the [`TemplateRuntimeApi`] under the `template_*` namespace, along with proofs of the
+//! pallet's values that `pallet_template_proof` verifies.
+
+#![warn(missing_docs)]
+
+use std::{marker::PhantomData, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{DeserializeOwned, Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use sc_client_api::ProofProvider;
+use serde::{Deserialize, Serialize};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::traits::Block as BlockT;
+
+pub use pallet_template_runtime_api::{
+	HistoryEntry, TemplateApi as TemplateRuntimeApi, TemplateProofApi as TemplateProofRuntimeApi,
+};
+
+/// A storage proof of an account's value at a block.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ValueProof<Hash> {
+	/// The block whose state the proof is for.
+	pub at: Hash,
+	/// The storage key of the value.
+	pub key: Bytes,
+	/// The trie nodes proving the value, or its absence, against the block's state root.
+	pub proof: Vec<Bytes>,
+}
+
+/// Template RPC methods.
+#[rpc(client, server)]
+pub trait TemplateApi<BlockHash, AccountId, BlockNumber> {
+	/// The most recently written value.
+	#[method(name = "template_latest")]
+	fn latest(
+		&self,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<HistoryEntry<BlockNumber, AccountId>>>;
+
+	/// Up to `limit` of the most recently written values, newest first.
+	#[method(name = "template_history")]
+	fn history(
+		&self,
+		limit: u32,
+		at: Option<BlockHash>,
+	) -> RpcResult<Vec<HistoryEntry<BlockNumber, AccountId>>>;
+
+	/// The last value written at or before `block`, if it is still in the pallet's history.
+	#[method(name = "template_valueAt")]
+	fn value_at(
+		&self,
+		block: BlockNumber,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<HistoryEntry<BlockNumber, AccountId>>>;
+
+	/// A proof of the value of `who`, to check against the state root of block `at`.
+	#[method(name = "template_getValueProof")]
+	fn get_value_proof(
+		&self,
+		who: AccountId,
+		at: Option<BlockHash>,
+	) -> RpcResult<ValueProof<BlockHash>>;
+}
+
+/// Provides RPC methods to query the template pallet.
+pub struct Template<C, Block> {
+	client: Arc<C>,
+	_marker: PhantomData<Block>,
+}
+
+impl<C, Block> Template<C, Block> {
+	/// Creates a new instance of the Template RPC handler.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to the runtime failed.
+	RuntimeError,
+	/// Reading a storage proof failed.
+	ProofError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::ProofError => 2,
+		}
+	}
+}
+
+fn call_error(error: Error, message: &'static str, err: impl std::fmt::Debug) -> JsonRpseeError {
+	CallError::Custom(ErrorObject::owned(error.into(), message, Some(format!("{:?}", err)))).into()
+}
+
+fn runtime_error(message: &'static str, err: impl std::fmt::Debug) -> JsonRpseeError {
+	call_error(Error::RuntimeError, message, err)
+}
+
+impl<C, Block, AccountId, BlockNumber>
+	TemplateApiServer<<Block as BlockT>::Hash, AccountId, BlockNumber> for Template<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + ProofProvider<Block>,
+	C: Send + Sync + 'static,
+	C::Api: TemplateRuntimeApi<Block, AccountId, BlockNumber>,
+	C::Api: TemplateProofRuntimeApi<Block, AccountId>,
+	AccountId: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+	BlockNumber: Codec + Serialize + DeserializeOwned + Send + Sync + 'static,
+{
+	fn latest(
+		&self,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<HistoryEntry<BlockNumber, AccountId>>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.latest(at)
+			.map_err(|e| runtime_error("Unable to query the latest value.", e))
+	}
+
+	fn history(
+		&self,
+		limit: u32,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Vec<HistoryEntry<BlockNumber, AccountId>>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.history(at, limit)
+			.map_err(|e| runtime_error("Unable to query the value history.", e))
+	}
+
+	fn value_at(
+		&self,
+		block: BlockNumber,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<Option<HistoryEntry<BlockNumber, AccountId>>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		self.client
+			.runtime_api()
+			.value_at(at, block)
+			.map_err(|e| runtime_error("Unable to query the value at the given block.", e))
+	}
+
+	fn get_value_proof(
+		&self,
+		who: AccountId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> RpcResult<ValueProof<<Block as BlockT>::Hash>> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		let key = self
+			.client
+			.runtime_api()
+			.value_key(at, who)
+			.map_err(|e| runtime_error("Unable to query the value's storage key.", e))?;
+		let proof = self
+			.client
+			.read_proof(at, &mut [key.as_slice()].into_iter())
+			.map_err(|e| call_error(Error::ProofError, "Unable to read the value's proof.", e))?;
+		Ok(ValueProof {
+			at,
+			key: key.into(),
+			proof: proof.into_iter_nodes().map(Into::into).collect(),
+		})
+	}
+}
@@ -0,0 +1,39 @@
+//! Runtime API definition for the template pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+pub use pallet_template::HistoryEntry;
+
+sp_api::decl_runtime_apis! {
+	/// Read access to the values recorded by the template pallet.
+	pub trait TemplateApi<AccountId, BlockNumber> where
+		AccountId: Codec,
+		BlockNumber: Codec,
+	{
+		/// The most recently written value.
+		fn latest() -> Option<HistoryEntry<BlockNumber, AccountId>>;
+		/// Up to `limit` of the most recently written values, newest first.
+		fn history(limit: u32) -> Vec<HistoryEntry<BlockNumber, AccountId>>;
+		/// The last value written at or before `block`, if it is still in the pallet's history.
+		fn value_at(block: BlockNumber) -> Option<HistoryEntry<BlockNumber, AccountId>>;
+	}
+
+	/// Read access to the template pallet's oracle.
+	pub trait TemplateOracleApi<Moment> where
+		Moment: Codec,
+	{
+		/// The last aggregated oracle value and its age, in the unit of the pallet's time source.
+		fn aggregated() -> Option<(u32, Moment)>;
+	}
+
+	/// What nodes need to export proofs of the template pallet's values.
+	pub trait TemplateProofApi<AccountId> where
+		AccountId: Codec,
+	{
+		/// The storage key of the value of `who`, to read a proof of it from a block's state.
+		fn value_key(who: AccountId) -> Vec<u8>;
+	}
+}
@@ -1,35 +1,572 @@
 //! Benchmarking setup for pallet-template
 #![cfg(feature = "runtime-benchmarks")]
 use super::*;
-use sp_std::vec;
+use sp_std::{vec, vec::Vec};
 
 #[allow(unused)]
 use crate::Pallet as Template;
+use codec::Decode;
 use frame_benchmarking::v2::*;
-use frame_system::RawOrigin;
+use frame_support::{
+	traits::{
+		fungible::{Inspect, InspectHold, Mutate, MutateHold},
+		Get, Hooks,
+	},
+	weights::Weight,
+	BoundedVec,
+};
+use frame_system::{pallet_prelude::BlockNumberFor, RawOrigin};
+use sp_runtime::{
+	traits::{IdentifyAccount, TrailingZeroInput},
+	Saturating,
+};
 
-#[benchmarks]
+/// Give `who` enough funds to pay for a value and a counter deposit.
+fn fund<T: Config<I>, I: 'static>(who: &T::AccountId) {
+	let amount = T::Currency::minimum_balance()
+		.saturating_add(T::ValueDeposit::get())
+		.saturating_add(T::CounterDeposit::get())
+		.saturating_mul(2u32.into());
+	T::Currency::set_balance(who, amount);
+}
+
+/// Fill [`History`] with `len` entries. Once it holds [`Config::MaxHistory`] entries, every write
+/// has to drop its oldest one.
+fn fill_history<T: Config<I>, I: 'static>(len: u32) {
+	let who: T::AccountId = account("writer", 0, 0);
+	let block = frame_system::Pallet::<T>::block_number();
+	let history =
+		(0..len).map(|value| HistoryEntry { block, who: who.clone(), value }).collect::<Vec<_>>();
+	History::<T, I>::put(BoundedVec::truncate_from(history));
+}
+
+/// Schedule changes for `n` funded accounts at block `at`, returning the accounts.
+fn fill_schedule<T: Config<I>, I: 'static>(at: BlockNumberFor<T>, n: u32) -> Vec<T::AccountId> {
+	let accounts = (0..n).map(|i| account::<T::AccountId>("scheduler", i, 0)).collect::<Vec<_>>();
+	let changes = accounts
+		.iter()
+		.map(|who| {
+			fund::<T, I>(who);
+			ScheduledChange { who: who.clone(), value: 100u32 }
+		})
+		.collect::<Vec<_>>();
+	Scheduled::<T, I>::insert(at, BoundedVec::truncate_from(changes));
+	accounts
+}
+
+/// Store values with a deposit for `n` accounts, expiring at the end of block `at`.
+fn fill_expiring<T: Config<I>, I: 'static>(at: BlockNumberFor<T>, n: u32) {
+	for i in 0..n {
+		let who = account::<T::AccountId>("expiring", i, 0);
+		fund::<T, I>(&who);
+		T::Currency::hold(&HoldReason::<I>::ValueDeposit.into(), &who, T::ValueDeposit::get())
+			.expect("the account was funded");
+		Something::<T, I>::insert(&who, 100u32);
+		ExpiresAt::<T, I>::insert(&who, at);
+		Expiring::<T, I>::try_append(at, &who).expect("at most MaxExpiringPerBlock are added");
+	}
+	ExpiryCursor::<T, I>::put(at);
+}
+
+/// A counter name of `len` bytes.
+fn counter_name<T: Config<I>, I: 'static>(len: u32) -> CounterNameOf<T, I> {
+	BoundedVec::truncate_from(vec![b'c'; len as usize])
+}
+
+/// Create a counter with a name of `len` bytes owned by the funded account `owner`, bounded so
+/// that any change of 1 stays in bounds.
+fn new_counter<T: Config<I>, I: 'static>(owner: &T::AccountId, len: u32) -> CounterNameOf<T, I> {
+	let name = counter_name::<T, I>(len);
+	fund::<T, I>(owner);
+	let deposit = T::CounterDeposit::get();
+	T::Currency::hold(&HoldReason::<I>::CounterDeposit.into(), owner, deposit)
+		.expect("the owner was funded");
+	let counter =
+		CounterInfo { owner: owner.clone(), deposit, value: 100, min: Some(0), max: Some(200) };
+	Counters::<T, I>::insert(&name, counter);
+	name
+}
+
+/// Fill [`Feeders`] with `n` accounts and let each of them feed the current round, returning the
+/// feeders.
+fn fill_feeders<T: Config<I>, I: 'static>(n: u32) -> Vec<T::AccountId> {
+	let mut feeders = (0..n).map(|i| account::<T::AccountId>("feeder", i, 0)).collect::<Vec<_>>();
+	feeders.sort();
+	let feeds = feeders.iter().zip(0u32..).map(|(who, value)| (who.clone(), value));
+	Feeds::<T, I>::put(BoundedVec::truncate_from(feeds.collect::<Vec<_>>()));
+	Feeders::<T, I>::put(BoundedVec::truncate_from(feeders.clone()));
+	feeders
+}
+
+/// Open a proposal by a funded account, ending after [`Config::VotingPeriod`] blocks.
+fn new_proposal<T: Config<I>, I: 'static>() -> ProposalIndex {
+	let proposer: T::AccountId = account("proposer", 0, 0);
+	T::Currency::set_balance(
+		&proposer,
+		T::Currency::minimum_balance().saturating_add(T::ProposalDeposit::get()),
+	);
+	let index = ProposalCount::<T, I>::get();
+	Pallet::<T, I>::propose(RawOrigin::Signed(proposer).into(), 100)
+		.expect("the proposer can pay the deposit");
+	index
+}
+
+/// Let a funded account vote on every open proposal but `index`, so that closing `index` has to
+/// keep the account frozen for the others. Returns the balance it votes with, which it can vote
+/// on `index` with too.
+fn vote_on_others<T: Config<I>, I: 'static>(
+	who: &T::AccountId,
+	index: ProposalIndex,
+) -> BalanceOf<T, I> {
+	let balance = T::ProposalDeposit::get();
+	T::Currency::set_balance(
+		who,
+		T::Currency::minimum_balance()
+			.saturating_add(balance)
+			.saturating_add(T::VoteFeeMargin::get()),
+	);
+	let open = Proposals::<T, I>::iter_keys().filter(|other| *other != index).collect::<Vec<_>>();
+	for other in open {
+		Pallet::<T, I>::vote(RawOrigin::Signed(who.clone()).into(), other, true, balance)
+			.expect("the account has not voted on the proposal yet");
+	}
+	balance
+}
+
+#[instance_benchmarks]
 mod benchmarks {
 	use super::*;
 
 	#[benchmark]
-	fn do_something() {
+	fn do_something(h: Linear<0, { T::MaxHistory::get() }>) -> Result<(), BenchmarkError> {
+		fill_history::<T, I>(h);
 		let value = 100u32.into();
+		let origin =
+			T::SetOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let caller = T::SetOrigin::ensure_origin(origin.clone()).map_err(|_| "bad origin")?;
+		fund::<T, I>(&caller);
+		#[extrinsic_call]
+		do_something(origin as T::RuntimeOrigin, value);
+
+		assert_eq!(Something::<T, I>::get(&caller), Some(value));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn cause_error(h: Linear<0, { T::MaxHistory::get() }>) -> Result<(), BenchmarkError> {
+		fill_history::<T, I>(h);
+		let origin =
+			T::SetOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let caller = T::SetOrigin::ensure_origin(origin.clone()).map_err(|_| "bad origin")?;
+		Something::<T, I>::insert(&caller, 100u32);
+		#[extrinsic_call]
+		cause_error(origin as T::RuntimeOrigin);
+
+		assert_eq!(Something::<T, I>::get(&caller), Some(101u32));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn clear_something() -> Result<(), BenchmarkError> {
+		let origin =
+			T::SetOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let caller = T::SetOrigin::ensure_origin(origin.clone()).map_err(|_| "bad origin")?;
+		fund::<T, I>(&caller);
+		// Clearing a value with a time-to-live also has to drop it from the expiry index.
+		Pallet::<T, I>::do_something_with_ttl(origin, 100u32, 10u32.into())?;
+		#[extrinsic_call]
+		clear_something(RawOrigin::Signed(caller.clone()));
+
+		assert_eq!(Something::<T, I>::get(&caller), None);
+		assert_eq!(
+			T::Currency::balance_on_hold(&HoldReason::<I>::ValueDeposit.into(), &caller),
+			0u32.into()
+		);
+		Ok(())
+	}
+
+	#[benchmark]
+	fn add_writer() {
+		let who: T::AccountId = account("writer", 0, 0);
+		#[extrinsic_call]
+		add_writer(RawOrigin::Root, who.clone());
+
+		assert!(AllowedWriters::<T, I>::contains_key(&who));
+	}
+
+	#[benchmark]
+	fn remove_writer() {
+		let who: T::AccountId = account("writer", 0, 0);
+		AllowedWriters::<T, I>::insert(&who, ());
+		#[extrinsic_call]
+		remove_writer(RawOrigin::Root, who.clone());
+
+		assert!(!AllowedWriters::<T, I>::contains_key(&who));
+	}
+
+	#[benchmark]
+	fn schedule_set() -> Result<(), BenchmarkError> {
+		let origin =
+			T::SetOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let at = frame_system::Pallet::<T>::block_number() + 10u32.into();
+		fill_schedule::<T, I>(at, T::MaxScheduledPerBlock::get().saturating_sub(1));
+		#[extrinsic_call]
+		schedule_set(origin as T::RuntimeOrigin, 100u32, at);
+
+		assert_eq!(Scheduled::<T, I>::get(at).len() as u32, T::MaxScheduledPerBlock::get());
+		Ok(())
+	}
+
+	#[benchmark]
+	fn cancel_scheduled() {
+		let at = frame_system::Pallet::<T>::block_number() + 10u32.into();
+		let accounts = fill_schedule::<T, I>(at, T::MaxScheduledPerBlock::get());
+		let caller = accounts.last().cloned().expect("at least one change can be scheduled");
+		#[extrinsic_call]
+		cancel_scheduled(RawOrigin::Signed(caller), at);
+
+		assert_eq!(Scheduled::<T, I>::get(at).len() as u32, T::MaxScheduledPerBlock::get() - 1);
+	}
+
+	#[benchmark]
+	fn on_initialize_idle() {
+		let now = frame_system::Pallet::<T>::block_number();
+		#[block]
+		{
+			Pallet::<T, I>::on_initialize(now);
+		}
+	}
+
+	#[benchmark]
+	fn on_initialize_apply(
+		n: Linear<1, { T::MaxScheduledPerBlock::get() }>,
+		h: Linear<0, { T::MaxHistory::get() }>,
+	) {
+		fill_history::<T, I>(h);
+		let now = frame_system::Pallet::<T>::block_number();
+		let accounts = fill_schedule::<T, I>(now, n);
+		#[block]
+		{
+			Pallet::<T, I>::on_initialize(now);
+		}
+
+		for who in accounts {
+			assert_eq!(Something::<T, I>::get(&who), Some(100u32));
+		}
+	}
+
+	#[benchmark]
+	fn submit_value_unsigned(h: Linear<0, { T::MaxHistory::get() }>) -> Result<(), BenchmarkError> {
+		fill_history::<T, I>(h);
+		// The signature is only checked when validating, so any key and signature will do.
+		let public = T::Public::decode(&mut TrailingZeroInput::zeroes())
+			.map_err(|_| "failed to decode public key")?;
+		let signature = T::Signature::decode(&mut TrailingZeroInput::zeroes())
+			.map_err(|_| "failed to decode signature")?;
+		let who = public.clone().into_account();
+		AllowedWriters::<T, I>::insert(&who, ());
+		T::SetOrigin::try_origin(RawOrigin::Signed(who.clone()).into())
+			.map_err(|_| BenchmarkError::Weightless)?;
+		fund::<T, I>(&who);
+		let block_number = frame_system::Pallet::<T>::block_number();
+		let payload = ValuePayload { block_number, public, value: 100u32 };
+		#[extrinsic_call]
+		submit_value_unsigned(RawOrigin::None, payload, signature);
+
+		assert_eq!(Something::<T, I>::get(&who), Some(100u32));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn create_counter(
+		n: Linear<1, { T::MaxCounterNameLength::get() }>,
+	) -> Result<(), BenchmarkError> {
+		let origin =
+			T::SetOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let caller = T::SetOrigin::ensure_origin(origin.clone()).map_err(|_| "bad origin")?;
+		fund::<T, I>(&caller);
+		let name = counter_name::<T, I>(n);
+		#[extrinsic_call]
+		create_counter(origin as T::RuntimeOrigin, name.clone(), 100, Some(0), Some(200));
+
+		assert!(Counters::<T, I>::contains_key(&name));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn increment(n: Linear<1, { T::MaxCounterNameLength::get() }>) {
 		let caller: T::AccountId = whitelisted_caller();
+		let name = new_counter::<T, I>(&caller, n);
 		#[extrinsic_call]
-		do_something(RawOrigin::Signed(caller), value);
+		increment(RawOrigin::Signed(caller), name.clone(), 1);
+
+		assert_eq!(Counters::<T, I>::get(&name).map(|counter| counter.value), Some(101));
+	}
+
+	#[benchmark]
+	fn decrement(n: Linear<1, { T::MaxCounterNameLength::get() }>) {
+		let caller: T::AccountId = whitelisted_caller();
+		let name = new_counter::<T, I>(&caller, n);
+		#[extrinsic_call]
+		decrement(RawOrigin::Signed(caller), name.clone(), 1);
+
+		assert_eq!(Counters::<T, I>::get(&name).map(|counter| counter.value), Some(99));
+	}
+
+	#[benchmark]
+	fn reset(n: Linear<1, { T::MaxCounterNameLength::get() }>) {
+		let caller: T::AccountId = whitelisted_caller();
+		let name = new_counter::<T, I>(&caller, n);
+		#[extrinsic_call]
+		reset(RawOrigin::Signed(caller), name.clone());
+
+		assert_eq!(Counters::<T, I>::get(&name).map(|counter| counter.value), Some(0));
+	}
+
+	#[benchmark]
+	fn transfer_ownership(n: Linear<1, { T::MaxCounterNameLength::get() }>) {
+		let caller: T::AccountId = whitelisted_caller();
+		let name = new_counter::<T, I>(&caller, n);
+		let new_owner: T::AccountId = account("owner", 0, 0);
+		fund::<T, I>(&new_owner);
+		#[extrinsic_call]
+		transfer_ownership(RawOrigin::Signed(caller), name.clone(), new_owner.clone());
+
+		assert_eq!(Counters::<T, I>::get(&name).map(|counter| counter.owner), Some(new_owner));
+	}
+
+	#[benchmark]
+	fn commit() -> Result<(), BenchmarkError> {
+		let origin =
+			T::SetOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let caller = T::SetOrigin::ensure_origin(origin.clone()).map_err(|_| "bad origin")?;
+		// Replacing an expired commitment is the most expensive path.
+		let expired = Commitment { hash: [0u8; 32], committed_at: 0u32.into() };
+		Commitments::<T, I>::insert(&caller, expired);
+		let now = T::RevealWindow::get().saturating_add(1u32.into());
+		frame_system::Pallet::<T>::set_block_number(now);
+		let hash = Pallet::<T, I>::commitment_hash(100, &[1u8; 32]);
+		#[extrinsic_call]
+		commit(origin as T::RuntimeOrigin, hash);
+
+		assert_eq!(Commitments::<T, I>::get(&caller).map(|c| c.hash), Some(hash));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn reveal(h: Linear<0, { T::MaxHistory::get() }>) -> Result<(), BenchmarkError> {
+		fill_history::<T, I>(h);
+		let origin =
+			T::SetOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let caller = T::SetOrigin::ensure_origin(origin.clone()).map_err(|_| "bad origin")?;
+		fund::<T, I>(&caller);
+		let salt = [1u8; 32];
+		let hash = Pallet::<T, I>::commitment_hash(100, &salt);
+		let committed_at = frame_system::Pallet::<T>::block_number();
+		Commitments::<T, I>::insert(&caller, Commitment { hash, committed_at });
+		frame_system::Pallet::<T>::set_block_number(committed_at + 1u32.into());
+		#[extrinsic_call]
+		reveal(origin as T::RuntimeOrigin, 100, salt);
+
+		assert_eq!(Something::<T, I>::get(&caller), Some(100));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn expire_commitment() {
+		let who: T::AccountId = account("committer", 0, 0);
+		let expired = Commitment { hash: [0u8; 32], committed_at: 0u32.into() };
+		Commitments::<T, I>::insert(&who, expired);
+		let now = T::RevealWindow::get().saturating_add(1u32.into());
+		#[block]
+		{
+			Pallet::<T, I>::expire_commitments(now, Weight::MAX);
+		}
+
+		assert!(!Commitments::<T, I>::contains_key(&who));
+	}
+
+	#[benchmark]
+	fn add_feeder() {
+		fill_feeders::<T, I>(T::MaxFeeders::get().saturating_sub(1));
+		let who: T::AccountId = account("new feeder", 0, 0);
+		#[extrinsic_call]
+		add_feeder(RawOrigin::Root, who.clone());
+
+		assert!(Feeders::<T, I>::get().contains(&who));
+	}
+
+	#[benchmark]
+	fn remove_feeder() {
+		let feeders = fill_feeders::<T, I>(T::MaxFeeders::get());
+		let who = feeders[0].clone();
+		#[extrinsic_call]
+		remove_feeder(RawOrigin::Root, who.clone());
+
+		assert!(!Feeders::<T, I>::get().contains(&who));
+	}
+
+	#[benchmark]
+	fn feed() {
+		let feeders = fill_feeders::<T, I>(T::MaxFeeders::get());
+		let who = feeders[feeders.len() - 1].clone();
+		Feeds::<T, I>::mutate(|feeds| feeds.retain(|(feeder, _)| *feeder != who));
+		#[extrinsic_call]
+		feed(RawOrigin::Signed(who.clone()), 100);
+
+		assert!(Feeds::<T, I>::get().contains(&(who, 100)));
+	}
+
+	#[benchmark]
+	fn close_round(n: Linear<0, { T::MaxFeeders::get() }>, h: Linear<0, { T::MaxHistory::get() }>) {
+		fill_history::<T, I>(h);
+		fill_feeders::<T, I>(n);
+		// The aggregated value replaces one with a time-to-live, which has to be dropped from the
+		// expiry index.
+		let oracle = T::OracleAccount::get();
+		let at = frame_system::Pallet::<T>::block_number().saturating_add(10u32.into());
+		ExpiresAt::<T, I>::insert(&oracle, at);
+		Expiring::<T, I>::try_append(at, &oracle).expect("the expiry index is empty");
+		#[block]
+		{
+			Pallet::<T, I>::close_round();
+		}
+
+		assert_eq!(Round::<T, I>::get(), 1);
+		assert_eq!(Aggregated::<T, I>::get().is_some(), n > 0);
+		assert_eq!(ExpiresAt::<T, I>::contains_key(&oracle), n == 0);
+	}
+
+	#[benchmark]
+	fn do_something_with_ttl(h: Linear<0, { T::MaxHistory::get() }>) -> Result<(), BenchmarkError> {
+		fill_history::<T, I>(h);
+		let origin =
+			T::SetOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let caller = T::SetOrigin::ensure_origin(origin.clone()).map_err(|_| "bad origin")?;
+		fund::<T, I>(&caller);
+		// Replacing a value with a time-to-live drops it from another block's expiry index.
+		Pallet::<T, I>::do_something_with_ttl(origin.clone(), 100, 20u32.into())?;
+		let ttl = 10u32.into();
+		let at = frame_system::Pallet::<T>::block_number() + ttl;
+		fill_expiring::<T, I>(at, T::MaxExpiringPerBlock::get().saturating_sub(1));
+		#[extrinsic_call]
+		do_something_with_ttl(origin as T::RuntimeOrigin, 200, ttl);
+
+		assert_eq!(ExpiresAt::<T, I>::get(&caller), Some(at));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn expire_value() {
+		let who = account::<T::AccountId>("expiring", 0, 0);
+		let at = frame_system::Pallet::<T>::block_number() + 1u32.into();
+		fill_expiring::<T, I>(at, 1);
+		#[block]
+		{
+			Pallet::<T, I>::expire_value(&who);
+		}
+
+		assert_eq!(Something::<T, I>::get(&who), None);
+	}
+
+	#[benchmark]
+	fn set_many(
+		n: Linear<1, { T::MaxBatch::get() }>,
+		h: Linear<0, { T::MaxHistory::get() }>,
+	) -> Result<(), BenchmarkError> {
+		fill_history::<T, I>(h);
+		let origin =
+			T::SetOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let caller = T::SetOrigin::ensure_origin(origin.clone()).map_err(|_| "bad origin")?;
+		fund::<T, I>(&caller);
+		let values = (0..n).map(|value| (caller.clone(), value)).collect::<Vec<_>>();
+		// Best-effort batches set each value in its own transactional layer.
+		#[extrinsic_call]
+		set_many(
+			origin as T::RuntimeOrigin,
+			BoundedVec::truncate_from(values),
+			BatchMode::BestEffort,
+		);
+
+		assert_eq!(Something::<T, I>::get(&caller), Some(n - 1));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn propose() {
+		// Fill all but one proposal slot, all ending in the same block.
+		for _ in 1..T::MaxProposals::get() {
+			new_proposal::<T, I>();
+		}
+		let caller: T::AccountId = whitelisted_caller();
+		fund::<T, I>(&caller);
+		let index = ProposalCount::<T, I>::get();
+		#[extrinsic_call]
+		propose(RawOrigin::Signed(caller), 100);
+
+		assert!(Proposals::<T, I>::contains_key(index));
+	}
+
+	#[benchmark]
+	fn vote() {
+		for _ in 0..T::MaxProposals::get() {
+			new_proposal::<T, I>();
+		}
+		let index = ProposalCount::<T, I>::get() - 1;
+		// The caller's other votes make its vote locks as long as they get.
+		let caller: T::AccountId = whitelisted_caller();
+		let balance = vote_on_others::<T, I>(&caller, index);
+		#[extrinsic_call]
+		vote(RawOrigin::Signed(caller.clone()), index, true, balance);
+
+		assert!(Votes::<T, I>::contains_key(index, &caller));
+	}
+
+	#[benchmark]
+	fn close_proposal(
+		n: Linear<0, { T::MaxVoters::get() }>,
+		h: Linear<0, { T::MaxHistory::get() }>,
+	) {
+		fill_history::<T, I>(h);
+		for _ in 0..T::MaxProposals::get() {
+			new_proposal::<T, I>();
+		}
+		let index = ProposalCount::<T, I>::get() - 1;
+		// Every voter stays frozen for its votes on the other proposals. The proposal passes, so
+		// its value is stored, replacing one with a time-to-live.
+		for i in 0..n {
+			let who: T::AccountId = account("voter", i, 0);
+			let balance = vote_on_others::<T, I>(&who, index);
+			Pallet::<T, I>::vote(RawOrigin::Signed(who).into(), index, true, balance)
+				.expect("the voter has not voted on the proposal yet");
+		}
+		let proposal_account = T::ProposalAccount::get();
+		let at = frame_system::Pallet::<T>::block_number().saturating_add(10u32.into());
+		ExpiresAt::<T, I>::insert(&proposal_account, at);
+		Expiring::<T, I>::try_append(at, &proposal_account).expect("the expiry index is empty");
+		#[block]
+		{
+			Pallet::<T, I>::close_proposal(index);
+		}
 
-		assert_eq!(Something::<T>::get(), Some(value));
+		assert!(!Proposals::<T, I>::contains_key(index));
+		assert_eq!(Votes::<T, I>::iter_prefix(index).count(), 0);
+		assert_eq!(Something::<T, I>::get(&proposal_account).is_some(), n > 0);
+		assert_eq!(ExpiresAt::<T, I>::contains_key(&proposal_account), n == 0);
 	}
 
 	#[benchmark]
-	fn cause_error() {
-		Something::<T>::put(100u32);
+	fn delete_counter(n: Linear<1, { T::MaxCounterNameLength::get() }>) {
 		let caller: T::AccountId = whitelisted_caller();
+		let name = new_counter::<T, I>(&caller, n);
 		#[extrinsic_call]
-		cause_error(RawOrigin::Signed(caller));
+		delete_counter(RawOrigin::Signed(caller.clone()), name.clone());
 
-		assert_eq!(Something::<T>::get(), Some(101u32));
+		assert!(!Counters::<T, I>::contains_key(&name));
+		assert_eq!(
+			T::Currency::balance_on_hold(&HoldReason::<I>::CounterDeposit.into(), &caller),
+			0u32.into()
+		);
 	}
 
 	impl_benchmark_test_suite!(Template, crate::mock::new_test_ext(), crate::mock::Test);
@@ -0,0 +1,216 @@
+//! A signed extension letting accounts make a few calls to the template pallet without fees.
+//!
+//! [`FreeCallQuota`] wraps the runtime's fee-charging extension, usually
+//! `pallet_transaction_payment::ChargeTransactionPayment`. A signed call writing a value to the
+//! pallet instance it is configured for, one of [`Pallet::do_something`],
+//! [`Pallet::do_something_with_ttl`], [`Pallet::clear_something`], [`Pallet::set_many`] and
+//! [`Pallet::feed`], skips the wrapped extension, and so its fees, while the sender has made fewer
+//! than [`Config::FreeCallsPerEra`] such calls in the current era of [`Config::FreeCallEra`]
+//! blocks. Once the quota is used up calls are charged as usual, and the transaction pool rejects
+//! the ones their sender cannot pay for with the custom `InvalidTransaction` code
+//! [`FREE_QUOTA_EXHAUSTED`].
+//!
+//! A free call that fails uses up the rest of its sender's quota for the era, so that failing
+//! calls, which cost the chain as much as succeeding ones, can't be sent without fees.
+//!
+//! A free call provides the tag `(b"tmpl-free", pallet index, sender, era, calls used)`, so the
+//! pool keeps at most one pending call per free slot: a second one validated against the same
+//! state conflicts with the first instead of also counting as free. Once the first is included the
+//! next call is validated against the updated count, and is rejected at validation if that count
+//! has reached the quota and the sender cannot pay.
+//!
+//! Every other call is passed to the wrapped extension unchanged. The wrapper encodes and
+//! describes itself in the metadata exactly like the extension it wraps, so wallets need not know
+//! about it.
+//!
+//! Free calls still take up block space, and the sender must still exist to have a nonce, e.g. by
+//! holding the existential deposit.
+
+use super::*;
+use codec::{Decode, Encode};
+use frame_support::{
+	traits::{Get, IsSubType, PalletInfoAccess},
+	CloneNoBound, DebugNoBound, EqNoBound, PartialEqNoBound,
+};
+use frame_system::pallet_prelude::BlockNumberFor;
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{
+		DispatchInfoOf, One, PostDispatchInfoOf, SaturatedConversion, SignedExtension,
+		SignedExtensionMetadata,
+	},
+	transaction_validity::{
+		InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransactionBuilder,
+	},
+	DispatchResult,
+};
+use sp_std::{marker::PhantomData, vec::Vec};
+
+/// What [`FreeCallQuota`] keeps from checking a call before dispatching it.
+pub enum FreeCallPre<AccountId, BlockNumber, Pre> {
+	/// A free call by `who` in free call era `era`.
+	Free { who: AccountId, era: BlockNumber },
+	/// A charged call, with what the wrapped extension's `pre_dispatch` returned.
+	Charged(Pre),
+}
+
+/// The `InvalidTransaction::Custom` code of calls rejected because their sender used up their
+/// free calls and cannot pay the fee.
+pub const FREE_QUOTA_EXHAUSTED: u8 = 1;
+
+/// Waives the fees of the wrapped extension `S` for the first [`Config::FreeCallsPerEra`] calls
+/// each account makes to instance `I` of the pallet in an era. See the [module docs](self).
+#[derive(Encode, Decode, CloneNoBound, EqNoBound, PartialEqNoBound, DebugNoBound, TypeInfo)]
+#[scale_info(skip_type_params(T, I))]
+pub struct FreeCallQuota<T: Config<I>, S: SignedExtension, I: 'static = ()>(
+	pub S,
+	PhantomData<(T, I)>,
+);
+
+impl<T: Config<I>, S: SignedExtension, I: 'static> From<S> for FreeCallQuota<T, S, I> {
+	fn from(inner: S) -> Self {
+		Self(inner, PhantomData)
+	}
+}
+
+impl<T: Config<I>, S: SignedExtension, I: 'static> FreeCallQuota<T, S, I> {
+	/// Whether `call` is one the pallet may waive the fees of.
+	fn is_free(call: &T::RuntimeCall) -> bool
+	where
+		T::RuntimeCall: IsSubType<Call<T, I>>,
+	{
+		matches!(
+			IsSubType::<Call<T, I>>::is_sub_type(call),
+			Some(
+				Call::do_something { .. } |
+					Call::do_something_with_ttl { .. } |
+					Call::clear_something { .. } |
+					Call::set_many { .. } |
+					Call::feed { .. }
+			)
+		)
+	}
+
+	/// The current era and the number of free calls `who` has made in it, if `call` can be free
+	/// and `who` has any left.
+	fn free_call(who: &T::AccountId, call: &T::RuntimeCall) -> Option<(BlockNumberFor<T>, u32)>
+	where
+		T::RuntimeCall: IsSubType<Call<T, I>>,
+	{
+		if !Self::is_free(call) {
+			return None
+		}
+		let era = frame_system::Pallet::<T>::block_number() / T::FreeCallEra::get();
+		let used = FreeCallsUsed::<T, I>::get(who)
+			.filter(|(last, _)| *last == era)
+			.map_or(0, |(_, used)| used);
+		(used < T::FreeCallsPerEra::get()).then_some((era, used))
+	}
+
+	/// Replace a failure to pay for a call that can be free with [`FREE_QUOTA_EXHAUSTED`].
+	fn quota_exhausted(
+		call: &T::RuntimeCall,
+		error: TransactionValidityError,
+	) -> TransactionValidityError
+	where
+		T::RuntimeCall: IsSubType<Call<T, I>>,
+	{
+		match error {
+			TransactionValidityError::Invalid(InvalidTransaction::Payment)
+				if Self::is_free(call) =>
+				InvalidTransaction::Custom(FREE_QUOTA_EXHAUSTED).into(),
+			error => error,
+		}
+	}
+}
+
+impl<T, S, I> SignedExtension for FreeCallQuota<T, S, I>
+where
+	T: Config<I> + Send + Sync,
+	T::RuntimeCall: IsSubType<Call<T, I>>,
+	S: SignedExtension<AccountId = T::AccountId, Call = T::RuntimeCall>,
+	I: Send + Sync + 'static,
+{
+	const IDENTIFIER: &'static str = S::IDENTIFIER;
+	type AccountId = T::AccountId;
+	type Call = T::RuntimeCall;
+	type AdditionalSigned = S::AdditionalSigned;
+	type Pre = FreeCallPre<T::AccountId, BlockNumberFor<T>, S::Pre>;
+
+	fn additional_signed(&self) -> Result<Self::AdditionalSigned, TransactionValidityError> {
+		self.0.additional_signed()
+	}
+
+	fn validate(
+		&self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> TransactionValidity {
+		match Self::free_call(who, call) {
+			Some((era, used)) => {
+				// Check the call again once its era, and so its quota, is over.
+				let era_end = era.saturating_add(One::one()).saturating_mul(T::FreeCallEra::get());
+				let now = frame_system::Pallet::<T>::block_number();
+				ValidTransactionBuilder::default()
+					.and_provides((
+						b"tmpl-free",
+						<Pallet<T, I> as PalletInfoAccess>::index(),
+						who,
+						era,
+						used,
+					))
+					.longevity(era_end.saturating_sub(now).saturated_into())
+					.build()
+			},
+			None =>
+				self.0.validate(who, call, info, len).map_err(|e| Self::quota_exhausted(call, e)),
+		}
+	}
+
+	fn pre_dispatch(
+		self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		match Self::free_call(who, call) {
+			Some((era, used)) => {
+				FreeCallsUsed::<T, I>::insert(who, (era, used + 1));
+				Ok(FreeCallPre::Free { who: who.clone(), era })
+			},
+			None => self
+				.0
+				.pre_dispatch(who, call, info, len)
+				.map(FreeCallPre::Charged)
+				.map_err(|e| Self::quota_exhausted(call, e)),
+		}
+	}
+
+	fn post_dispatch(
+		pre: Option<Self::Pre>,
+		info: &DispatchInfoOf<Self::Call>,
+		post_info: &PostDispatchInfoOf<Self::Call>,
+		len: usize,
+		result: &DispatchResult,
+	) -> Result<(), TransactionValidityError> {
+		match pre {
+			// A free call has nothing to refund, and one that failed uses up the quota.
+			Some(FreeCallPre::Free { who, era }) => {
+				if result.is_err() {
+					FreeCallsUsed::<T, I>::insert(who, (era, T::FreeCallsPerEra::get()));
+				}
+				Ok(())
+			},
+			Some(FreeCallPre::Charged(pre)) =>
+				S::post_dispatch(Some(pre), info, post_info, len, result),
+			None => S::post_dispatch(None, info, post_info, len, result),
+		}
+	}
+
+	fn metadata() -> Vec<SignedExtensionMetadata> {
+		S::metadata()
+	}
+}
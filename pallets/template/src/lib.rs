@@ -7,12 +7,31 @@
 //! ## Overview
 //!
 //! This template pallet contains basic examples of:
-//! - declaring a storage item that stores a single `u32` value
+//! - declaring a storage map that stores a `u32` value for each account
 //! - declaring and using events
 //! - declaring and using errors
-//! - a dispatchable function that allows a user to set a new value to storage and emits an event
-//!   upon success
+//! - a dispatchable function that allows a user to set their own value in storage and emits an
+//!   event upon success
 //! - another dispatchable function that causes a custom error to be thrown
+//! - a dispatchable function that allows a user to remove their value from storage
+//! - a bounded history of written values, exposed to clients through a runtime API
+//! - proofs of a block's values against its state root, see [`Pallet::value_key`]
+//! - a configurable origin deciding who may write, see [`Config::SetOrigin`] and [`EnsureWriter`]
+//! - a deposit held from each account while it has a value stored, see [`Config::ValueDeposit`]
+//! - value changes scheduled for a future block and applied in `on_initialize`
+//! - values with a time-to-live, removed in `on_idle` once they expire
+//! - a registry of named counters with checked, optionally bounded arithmetic, see [`Counters`]
+//! - a commit-reveal flow that keeps a value hidden until it is applied, cleaned up in `on_idle`
+//! - an oracle aggregating the values of root-managed feeders each round, see [`Feeders`]
+//! - a root-only call setting many values at once, atomically or best-effort, see [`BatchMode`]
+//! - proposals to set a value, voted on with frozen balances and enacted when they pass, see
+//!   [`Proposals`]
+//! - a signed extension waiving fees for a few calls per account and era, see [`extension`]
+//! - an offchain worker submitting values in signed or unsigned transactions, see [`offchain`]
+//! - a storage migration, see [`migrations`]
+//! - initial values and writers set in the chain's genesis, see [`GenesisConfig`]
+//! - an instantiable pallet: a runtime can add it several times, each instance with its own
+//!   config and storage
 //!
 //! Each pallet section is annotated with an attribute using the `#[pallet::...]` procedural macro.
 //! This macro generates the necessary code for a pallet to be aggregated into a FRAME runtime.
@@ -42,36 +61,80 @@
 // Re-export pallet items so that they can be accessed from the crate namespace.
 pub use pallet::*;
 
+use frame_support::traits::EnsureOrigin;
+use frame_system::RawOrigin;
+
 // FRAME pallets require their own "mock runtimes" to be able to run unit tests. This module
-// contains a mock runtime specific for testing this pallet's functionality.
-#[cfg(test)]
-mod mock;
+// contains a mock runtime specific for testing this pallet's functionality. The `fuzzing`
+// feature makes it public for the fuzzer in `fuzz`.
+#[cfg(any(test, feature = "fuzzing"))]
+pub mod mock;
 
 // This module contains the unit tests for this pallet.
 // Learn about pallet unit testing here: https://docs.substrate.io/test/unit-testing/
 #[cfg(test)]
 mod tests;
 
+// Property tests that check random sequences of calls and blocks against a model of the pallet.
+#[cfg(test)]
+mod proptests;
+
 // Every callable function or "dispatchable" a pallet exposes must have weight values that correctly
 // estimate a dispatchable's execution time. The benchmarking module is used to calculate weights
 // for each dispatchable and generates this pallet's weight.rs file. Learn more about benchmarking here: https://docs.substrate.io/test/benchmark/
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
+pub mod extension;
+pub mod migrations;
+pub mod offchain;
+mod types;
 pub mod weights;
+pub use extension::{FreeCallPre, FreeCallQuota, FREE_QUOTA_EXHAUSTED};
+pub use offchain::{crypto, ValuePayload, KEY_TYPE};
+pub use types::*;
 pub use weights::*;
 
+/// The log target of this pallet.
+pub(crate) const LOG_TARGET: &str = "runtime::template";
+
 // All pallet logic is defined in its own module and must be annotated by the `pallet` attribute.
 #[frame_support::pallet]
 pub mod pallet {
 	// Import various useful types required by all FRAME pallets.
 	use super::*;
-	use frame_support::pallet_prelude::*;
-	use frame_system::pallet_prelude::*;
+	#[cfg(any(feature = "try-runtime", feature = "fuzzing", test))]
+	use frame_support::traits::fungible::InspectFreeze;
+	use frame_support::{
+		pallet_prelude::*,
+		storage::{with_transaction, TransactionOutcome},
+		traits::{
+			fungible::{Inspect, InspectHold, Mutate, MutateFreeze, MutateHold},
+			tokens::{Fortitude, Precision, Preservation, Restriction},
+			Time,
+		},
+	};
+	use frame_system::{
+		offchain::{AppCrypto, CreateSignedTransaction, SignedPayload},
+		pallet_prelude::*,
+	};
+	#[cfg(any(feature = "try-runtime", feature = "fuzzing", test))]
+	use sp_runtime::TryRuntimeError;
+	use sp_runtime::{
+		traits::{IdentifyAccount, Saturating, Zero},
+		PerThing, Perbill,
+	};
+	#[cfg(any(feature = "try-runtime", feature = "fuzzing", test))]
+	use sp_std::collections::btree_map::BTreeMap;
+	use sp_std::vec::Vec;
+
+	/// The current storage version. Bump it together with a new migration in [`migrations`].
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
 
 	// The `Pallet` struct serves as a placeholder to implement traits, methods and dispatchables
 	// (`Call`s) in this pallet.
 	#[pallet::pallet]
-	pub struct Pallet<T>(_);
+	#[pallet::storage_version(STORAGE_VERSION)]
+	pub struct Pallet<T, I = ()>(PhantomData<(T, I)>);
 
 	/// The pallet's configuration trait.
 	///
@@ -79,21 +142,344 @@ pub mod pallet {
 	/// These types are defined generically and made concrete when the pallet is declared in the
 	/// `runtime/src/lib.rs` file of your chain.
 	#[pallet::config]
-	pub trait Config: frame_system::Config {
+	pub trait Config<I: 'static = ()>:
+		CreateSignedTransaction<Call<Self, I>> + frame_system::Config
+	{
 		/// The overarching runtime event type.
-		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		type RuntimeEvent: From<Event<Self, I>>
+			+ IsType<<Self as frame_system::Config>::RuntimeEvent>;
 		/// A type representing the weights required by the dispatchables of this pallet.
 		type WeightInfo: WeightInfo;
+		/// The maximum number of entries kept in [`History`]. Once full, the oldest entry is
+		/// dropped for each new one.
+		///
+		/// Every write is weighed as if the history were full, so a shorter history makes writes
+		/// cheaper.
+		#[pallet::constant]
+		type MaxHistory: Get<u32>;
+		/// The origin allowed to write values, which resolves to the account the value is
+		/// stored under.
+		///
+		/// Use [`frame_system::EnsureSigned`] to let any signer write, [`EnsureWriter`] to only
+		/// allow accounts in [`AllowedWriters`], or `EnsureRootWithSuccess` to only allow root.
+		type SetOrigin: EnsureOrigin<Self::RuntimeOrigin, Success = Self::AccountId>;
+		/// The currency that deposits are held in.
+		type Currency: Inspect<Self::AccountId>
+			+ Mutate<Self::AccountId>
+			+ MutateHold<Self::AccountId, Reason = Self::RuntimeHoldReason>
+			+ MutateFreeze<Self::AccountId, Id = Self::RuntimeFreezeReason>;
+		/// The overarching hold reason.
+		type RuntimeHoldReason: From<HoldReason<I>>;
+		/// The overarching freeze reason.
+		type RuntimeFreezeReason: From<FreezeReason<I>>;
+		/// The amount held from an account while it has a value in [`Something`].
+		#[pallet::constant]
+		type ValueDeposit: Get<BalanceOf<Self, I>>;
+		/// The maximum number of changes that can be scheduled for a single block. This bounds
+		/// the work done in `on_initialize`.
+		#[pallet::constant]
+		type MaxScheduledPerBlock: Get<u32>;
+		/// The keys the offchain worker signs its transactions and payloads with.
+		type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+		/// The minimum number of blocks between two accepted unsigned submissions, and between two
+		/// signed submissions by the offchain worker.
+		#[pallet::constant]
+		type UnsignedInterval: Get<BlockNumberFor<Self>>;
+		/// The priority of unsigned submissions in the transaction pool.
+		#[pallet::constant]
+		type UnsignedPriority: Get<TransactionPriority>;
+		/// The maximum length of a counter name, in bytes.
+		#[pallet::constant]
+		type MaxCounterNameLength: Get<u32>;
+		/// The amount held from an account for each counter it owns in [`Counters`].
+		#[pallet::constant]
+		type CounterDeposit: Get<BalanceOf<Self, I>>;
+		/// The number of blocks after a commitment during which it can be revealed.
+		#[pallet::constant]
+		type RevealWindow: Get<BlockNumberFor<Self>>;
+		/// The maximum number of values that can expire in a single block.
+		#[pallet::constant]
+		type MaxExpiringPerBlock: Get<u32>;
+		/// The source of the time an aggregated value was finalized at, usually
+		/// `pallet_timestamp`.
+		type Time: Time;
+		/// The maximum number of accounts in [`Feeders`].
+		#[pallet::constant]
+		type MaxFeeders: Get<u32>;
+		/// The number of blocks in an oracle round. A round is finalized at the end of every block
+		/// whose number is a multiple of it.
+		#[pallet::constant]
+		type RoundLength: Get<BlockNumberFor<Self>>;
+		/// The account the aggregated value is stored under in [`Something`].
+		#[pallet::constant]
+		type OracleAccount: Get<Self::AccountId>;
+		/// The account whose value in [`Something`] passed proposals set.
+		#[pallet::constant]
+		type ProposalAccount: Get<Self::AccountId>;
+		/// The maximum number of values a single [`Pallet::set_many`] call sets.
+		#[pallet::constant]
+		type MaxBatch: Get<u32>;
+		/// The number of calls writing a value to this pallet each account makes without fees per
+		/// era, when the runtime's transactions are checked by [`FreeCallQuota`].
+		#[pallet::constant]
+		type FreeCallsPerEra: Get<u32>;
+		/// The number of blocks in a free call era. An era starts at every block whose number is
+		/// a multiple of it.
+		#[pallet::constant]
+		type FreeCallEra: Get<BlockNumberFor<Self>>;
+		/// The amount held from an account for each of its proposals until the vote on it ends.
+		#[pallet::constant]
+		type ProposalDeposit: Get<BalanceOf<Self, I>>;
+		/// The number of blocks a proposal is open for votes.
+		#[pallet::constant]
+		type VotingPeriod: Get<BlockNumberFor<Self>>;
+		/// The share of the balance voting on a proposal that has to vote for it for it to pass.
+		#[pallet::constant]
+		type ApprovalThreshold: Get<Perbill>;
+		/// The maximum number of proposals open for votes at once.
+		#[pallet::constant]
+		type MaxProposals: Get<u32>;
+		/// The maximum number of accounts that can vote on a proposal.
+		#[pallet::constant]
+		type MaxVoters: Get<u32>;
+		/// The part of an account's balance its votes leave unfrozen, so that it can still pay
+		/// transaction fees while they are live.
+		#[pallet::constant]
+		type VoteFeeMargin: Get<BalanceOf<Self, I>>;
+	}
+
+	/// A reason for this pallet placing a hold on funds.
+	#[pallet::composite_enum]
+	pub enum HoldReason<I: 'static = ()> {
+		/// The account has a value stored in [`Something`].
+		ValueDeposit,
+		/// The account has a proposal open for votes in [`Proposals`].
+		ProposalDeposit,
+		/// The account owns counters in [`Counters`].
+		CounterDeposit,
 	}
 
+	/// A reason for this pallet freezing funds.
+	#[pallet::composite_enum]
+	pub enum FreezeReason<I: 'static = ()> {
+		/// The account voted on proposals still open for votes, see [`VoteLocks`].
+		Vote,
+	}
+
+	/// A [`HistoryEntry`] as stored by this pallet.
+	pub type HistoryEntryOf<T> =
+		HistoryEntry<BlockNumberFor<T>, <T as frame_system::Config>::AccountId>;
+
+	/// A [`ScheduledChange`] as stored by this pallet.
+	pub type ScheduledChangeOf<T> = ScheduledChange<<T as frame_system::Config>::AccountId>;
+
+	/// The name of a counter in [`Counters`].
+	pub type CounterNameOf<T, I = ()> = BoundedVec<u8, <T as Config<I>>::MaxCounterNameLength>;
+
+	/// A [`CounterInfo`] as stored by this pallet.
+	pub type CounterInfoOf<T, I = ()> =
+		CounterInfo<<T as frame_system::Config>::AccountId, BalanceOf<T, I>>;
+
+	/// A [`Commitment`] as stored by this pallet.
+	pub type CommitmentOf<T> = Commitment<BlockNumberFor<T>>;
+
+	/// The moment type of [`Config::Time`].
+	pub type MomentOf<T, I = ()> = <<T as Config<I>>::Time as Time>::Moment;
+
+	/// An [`AggregatedValue`] as stored by this pallet.
+	pub type AggregatedValueOf<T, I = ()> = AggregatedValue<MomentOf<T, I>>;
+
+	/// The accounts and values set by a [`Pallet::set_many`] call.
+	pub type BatchOf<T, I = ()> =
+		BoundedVec<(<T as frame_system::Config>::AccountId, u32), <T as Config<I>>::MaxBatch>;
+
+	/// The index of a proposal in [`Proposals`].
+	pub type ProposalIndex = u32;
+
+	/// A [`Proposal`] as stored by this pallet.
+	pub type ProposalOf<T, I = ()> =
+		Proposal<<T as frame_system::Config>::AccountId, BalanceOf<T, I>, BlockNumberFor<T>>;
+
+	/// A [`Vote`] as stored by this pallet.
+	pub type VoteOf<T, I = ()> = Vote<BalanceOf<T, I>>;
+
+	/// The balance type of [`Config::Currency`].
+	pub type BalanceOf<T, I = ()> =
+		<<T as Config<I>>::Currency as Inspect<<T as frame_system::Config>::AccountId>>::Balance;
+
 	/// A storage item for this pallet.
 	///
-	/// In this template, we are declaring a storage item called `Something` that stores a single
-	/// `u32` value. Learn more about runtime storage here: <https://docs.substrate.io/build/runtime-storage/>
+	/// In this template, we are declaring a storage map called `Something` that stores a `u32`
+	/// value for each account that has set one. Learn more about runtime storage here: <https://docs.substrate.io/build/runtime-storage/>
 	/// The [`getter`] macro generates a function to conveniently retrieve the value from storage.
 	#[pallet::storage]
 	#[pallet::getter(fn something)]
-	pub type Something<T> = StorageValue<_, u32>;
+	pub type Something<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32>;
+
+	/// The accounts accepted by [`EnsureWriter`], managed by root.
+	#[pallet::storage]
+	pub type AllowedWriters<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, ()>;
+
+	/// The most recently written values, oldest first.
+	#[pallet::storage]
+	pub type History<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BoundedVec<HistoryEntryOf<T>, T::MaxHistory>, ValueQuery>;
+
+	/// Value changes to apply at the start of a block, keyed by that block.
+	#[pallet::storage]
+	pub type Scheduled<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		BoundedVec<ScheduledChangeOf<T>, T::MaxScheduledPerBlock>,
+		ValueQuery,
+	>;
+
+	/// The block at the end of which each account's value expires, for values written with a
+	/// time-to-live.
+	#[pallet::storage]
+	pub type ExpiresAt<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, BlockNumberFor<T>>;
+
+	/// The accounts whose values expire at the end of a block, keyed by that block.
+	#[pallet::storage]
+	pub type Expiring<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		BoundedVec<T::AccountId, T::MaxExpiringPerBlock>,
+		ValueQuery,
+	>;
+
+	/// The first block in [`Expiring`] that `on_idle` has not finished cleaning up, if any value
+	/// was ever written with a time-to-live.
+	#[pallet::storage]
+	pub type ExpiryCursor<T: Config<I>, I: 'static = ()> = StorageValue<_, BlockNumberFor<T>>;
+
+	/// The registry of named counters.
+	#[pallet::storage]
+	pub type Counters<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, CounterNameOf<T, I>, CounterInfoOf<T, I>>;
+
+	/// The value each account has committed to but not yet revealed.
+	#[pallet::storage]
+	pub type Commitments<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, CommitmentOf<T>>;
+
+	/// The last account whose commitment `on_idle` checked for expiry, if it stopped before the
+	/// end of [`Commitments`].
+	#[pallet::storage]
+	pub type CommitmentCursor<T: Config<I>, I: 'static = ()> = StorageValue<_, T::AccountId>;
+
+	/// The accounts allowed to feed values to the oracle, managed by root. Kept sorted.
+	#[pallet::storage]
+	pub type Feeders<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BoundedVec<T::AccountId, T::MaxFeeders>, ValueQuery>;
+
+	/// The values fed in the current round, at most one per feeder.
+	#[pallet::storage]
+	pub type Feeds<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BoundedVec<(T::AccountId, u32), T::MaxFeeders>, ValueQuery>;
+
+	/// The index of the current oracle round.
+	#[pallet::storage]
+	pub type Round<T: Config<I>, I: 'static = ()> = StorageValue<_, u32, ValueQuery>;
+
+	/// The value aggregated in the last round any feeder took part in.
+	#[pallet::storage]
+	pub type Aggregated<T: Config<I>, I: 'static = ()> = StorageValue<_, AggregatedValueOf<T, I>>;
+
+	/// The free call era each account last made a free call in, and how many it made in it.
+	#[pallet::storage]
+	pub type FreeCallsUsed<T: Config<I>, I: 'static = ()> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, (BlockNumberFor<T>, u32)>;
+
+	/// The index the next proposal gets.
+	#[pallet::storage]
+	pub type ProposalCount<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, ProposalIndex, ValueQuery>;
+
+	/// The proposals open for votes.
+	#[pallet::storage]
+	pub type Proposals<T: Config<I>, I: 'static = ()> =
+		CountedStorageMap<_, Twox64Concat, ProposalIndex, ProposalOf<T, I>>;
+
+	/// The proposals whose vote ends at the start of a block, keyed by that block.
+	#[pallet::storage]
+	pub type ProposalsEnding<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Twox64Concat,
+		BlockNumberFor<T>,
+		BoundedVec<ProposalIndex, T::MaxProposals>,
+		ValueQuery,
+	>;
+
+	/// The votes on each proposal in [`Proposals`].
+	#[pallet::storage]
+	pub type Votes<T: Config<I>, I: 'static = ()> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		ProposalIndex,
+		Blake2_128Concat,
+		T::AccountId,
+		VoteOf<T, I>,
+	>;
+
+	/// The proposals each account voted on that are still open, with the balance it voted with.
+	///
+	/// The account's [`FreezeReason::Vote`] freeze is the largest of these balances.
+	#[pallet::storage]
+	pub type VoteLocks<T: Config<I>, I: 'static = ()> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::AccountId,
+		BoundedVec<(ProposalIndex, BalanceOf<T, I>), T::MaxProposals>,
+		ValueQuery,
+	>;
+
+	/// The first block at which another unsigned submission is accepted.
+	#[pallet::storage]
+	pub type NextUnsignedAt<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, BlockNumberFor<T>, ValueQuery>;
+
+	/// The values and writers a chain starts with.
+	#[pallet::genesis_config]
+	#[derive(frame_support::DefaultNoBound)]
+	pub struct GenesisConfig<T: Config<I>, I: 'static = ()> {
+		#[serde(skip)]
+		pub _phantom: PhantomData<I>,
+		/// Initial values, each stored as if its account had called [`Pallet::do_something`].
+		///
+		/// Every account must appear at most once and be able to pay [`Config::ValueDeposit`].
+		pub values: Vec<(T::AccountId, u32)>,
+		/// The initial [`AllowedWriters`]. Every account must appear at most once.
+		pub writers: Vec<T::AccountId>,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config<I>, I: 'static> BuildGenesisConfig for GenesisConfig<T, I> {
+		fn build(&self) {
+			for (who, value) in &self.values {
+				assert!(
+					!Something::<T, I>::contains_key(who),
+					"account {:?} has more than one genesis value",
+					who
+				);
+				Pallet::<T, I>::set_value(who, *value)
+					.expect("accounts with a genesis value must be able to pay the deposit");
+			}
+			for who in &self.writers {
+				assert!(
+					!AllowedWriters::<T, I>::contains_key(who),
+					"account {:?} is a genesis writer more than once",
+					who
+				);
+				AllowedWriters::<T, I>::insert(who, ());
+			}
+		}
+	}
 
 	/// Events that functions in this pallet can emit.
 	///
@@ -107,13 +493,219 @@ pub mod pallet {
 	/// [`Config`] trait) and deposit it using [`frame_system::Pallet::deposit_event`].
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
-	pub enum Event<T: Config> {
+	pub enum Event<T: Config<I>, I: 'static = ()> {
 		/// A user has successfully set a new value.
 		SomethingStored {
-			/// The new value set.
-			something: u32,
 			/// The account who set the new value.
 			who: T::AccountId,
+			/// The value previously stored for the account, if any.
+			old: Option<u32>,
+			/// The new value set.
+			new: u32,
+		},
+		/// A user has removed their value.
+		SomethingCleared {
+			/// The account who removed the value.
+			who: T::AccountId,
+			/// The value that was removed.
+			old: u32,
+		},
+		/// An account was added to [`AllowedWriters`].
+		WriterAdded {
+			/// The account added.
+			who: T::AccountId,
+		},
+		/// An account was removed from [`AllowedWriters`].
+		WriterRemoved {
+			/// The account removed.
+			who: T::AccountId,
+		},
+		/// A value change was scheduled for a future block.
+		ValueScheduled {
+			/// The account the value will be set for.
+			who: T::AccountId,
+			/// The value to set.
+			value: u32,
+			/// The block at the start of which the value will be set.
+			at: BlockNumberFor<T>,
+		},
+		/// A scheduled value change was cancelled.
+		ScheduleCancelled {
+			/// The account the value would have been set for.
+			who: T::AccountId,
+			/// The block the change was scheduled for.
+			at: BlockNumberFor<T>,
+		},
+		/// A scheduled value change was applied.
+		ScheduledValueApplied {
+			/// The account the value was set for.
+			who: T::AccountId,
+			/// The value previously stored for the account, if any.
+			old: Option<u32>,
+			/// The new value set.
+			new: u32,
+		},
+		/// A value's time-to-live ran out and it has been removed, releasing its deposit.
+		ValueExpired {
+			/// The account the value was stored for.
+			who: T::AccountId,
+			/// The value removed.
+			value: u32,
+		},
+		/// A counter was created.
+		CounterCreated {
+			/// The counter's name.
+			name: CounterNameOf<T, I>,
+			/// The account that owns the counter.
+			owner: T::AccountId,
+			/// The counter's initial value.
+			value: u64,
+		},
+		/// A counter was incremented, decremented or reset.
+		CounterUpdated {
+			/// The counter's name.
+			name: CounterNameOf<T, I>,
+			/// The counter's previous value.
+			old: u64,
+			/// The counter's new value.
+			new: u64,
+		},
+		/// A counter was handed to a new owner.
+		CounterOwnershipTransferred {
+			/// The counter's name.
+			name: CounterNameOf<T, I>,
+			/// The previous owner.
+			from: T::AccountId,
+			/// The new owner.
+			to: T::AccountId,
+		},
+		/// A counter was deleted, releasing its deposit.
+		CounterDeleted {
+			/// The counter's name.
+			name: CounterNameOf<T, I>,
+			/// The account that owned the counter.
+			owner: T::AccountId,
+		},
+		/// An account committed to a hidden value.
+		Committed {
+			/// The account that committed.
+			who: T::AccountId,
+			/// The last block in which the value can be revealed.
+			reveal_by: BlockNumberFor<T>,
+		},
+		/// A commitment was not revealed in time and has been removed.
+		CommitmentExpired {
+			/// The account whose commitment expired.
+			who: T::AccountId,
+		},
+		/// An account was added to [`Feeders`].
+		FeederAdded {
+			/// The account added.
+			who: T::AccountId,
+		},
+		/// An account was removed from [`Feeders`], dropping its value for the current round.
+		FeederRemoved {
+			/// The account removed.
+			who: T::AccountId,
+		},
+		/// A feeder submitted its value for the current round.
+		Fed {
+			/// The feeder.
+			who: T::AccountId,
+			/// The round the value was fed in.
+			round: u32,
+			/// The value fed.
+			value: u32,
+		},
+		/// An oracle round was closed.
+		RoundFinalized {
+			/// The round closed.
+			round: u32,
+			/// The median of the values fed, stored under [`Config::OracleAccount`]. `None` if no
+			/// feeder took part.
+			value: Option<u32>,
+			/// The number of feeders that took part.
+			fed: u32,
+			/// The number of feeders at the end of the round.
+			feeders: u32,
+		},
+		/// A scheduled value change could not be applied.
+		ScheduledValueFailed {
+			/// The account the value would have been set for.
+			who: T::AccountId,
+			/// The value that was not set.
+			value: u32,
+			/// Why the value could not be set.
+			error: DispatchError,
+		},
+		/// A value of a [`Pallet::set_many`] batch was set.
+		BatchItemSucceeded {
+			/// The position of the value in the batch.
+			index: u32,
+			/// The account the value was set for.
+			who: T::AccountId,
+			/// The account's previous value, if it had one.
+			old: Option<u32>,
+			/// The value set.
+			new: u32,
+		},
+		/// A value of a [`BatchMode::BestEffort`] batch could not be set.
+		BatchItemFailed {
+			/// The position of the value in the batch.
+			index: u32,
+			/// The account the value would have been set for.
+			who: T::AccountId,
+			/// The value that was not set.
+			value: u32,
+			/// Why the value could not be set.
+			error: DispatchError,
+		},
+		/// A proposal was made and is open for votes.
+		Proposed {
+			/// The proposal's index in [`Proposals`].
+			index: ProposalIndex,
+			/// The account that made the proposal.
+			proposer: T::AccountId,
+			/// The value proposed.
+			value: u32,
+			/// The block at the start of which the vote ends.
+			end: BlockNumberFor<T>,
+		},
+		/// An account voted on a proposal.
+		Voted {
+			/// The proposal voted on.
+			index: ProposalIndex,
+			/// The account that voted.
+			who: T::AccountId,
+			/// Whether the vote is for the proposal.
+			aye: bool,
+			/// The balance voted with.
+			balance: BalanceOf<T, I>,
+			/// The total balance voting for the proposal so far.
+			ayes: BalanceOf<T, I>,
+			/// The total balance voting against the proposal so far.
+			nays: BalanceOf<T, I>,
+		},
+		/// A proposal's vote ended with enough support, and its value was stored under
+		/// [`Config::ProposalAccount`].
+		ProposalPassed {
+			/// The proposal's index.
+			index: ProposalIndex,
+			/// The value stored.
+			value: u32,
+			/// The total balance that voted for the proposal.
+			ayes: BalanceOf<T, I>,
+			/// The total balance that voted against the proposal.
+			nays: BalanceOf<T, I>,
+		},
+		/// A proposal's vote ended without enough support.
+		ProposalRejected {
+			/// The proposal's index.
+			index: ProposalIndex,
+			/// The total balance that voted for the proposal.
+			ayes: BalanceOf<T, I>,
+			/// The total balance that voted against the proposal.
+			nays: BalanceOf<T, I>,
 		},
 	}
 
@@ -126,11 +718,144 @@ pub mod pallet {
 	/// This type of runtime error can be up to 4 bytes in size should you want to return additional
 	/// information.
 	#[pallet::error]
-	pub enum Error<T> {
-		/// The value retrieved was `None` as no value was previously set.
+	pub enum Error<T, I = ()> {
+		/// The value retrieved was `None` as the caller has not set a value.
 		NoneValue,
 		/// There was an attempt to increment the value in storage over `u32::MAX`.
 		StorageOverflow,
+		/// The account is already in [`AllowedWriters`].
+		AlreadyWriter,
+		/// The account is not in [`AllowedWriters`].
+		NotWriter,
+		/// Changes can only be scheduled for future blocks.
+		ScheduleInPast,
+		/// The block already has [`Config::MaxScheduledPerBlock`] changes scheduled.
+		TooManyScheduled,
+		/// The caller already has a change scheduled for the block.
+		AlreadyScheduled,
+		/// The caller has no change scheduled for the block.
+		NotScheduled,
+		/// A value's time-to-live must be at least one block.
+		ZeroTtl,
+		/// The block already has [`Config::MaxExpiringPerBlock`] values expiring in it.
+		TooManyExpiring,
+		/// A counter with this name already exists.
+		CounterExists,
+		/// No counter with this name exists.
+		UnknownCounter,
+		/// The caller does not own the counter.
+		NotCounterOwner,
+		/// There was an attempt to decrement a counter below zero.
+		Underflow,
+		/// The counter's value would leave its bounds.
+		OutOfBounds,
+		/// The caller has a commitment that can still be revealed.
+		AlreadyCommitted,
+		/// The caller has no commitment.
+		NoCommitment,
+		/// A commitment can only be revealed in a later block than it was made in.
+		RevealTooEarly,
+		/// The commitment's reveal window has passed.
+		RevealTooLate,
+		/// The value and salt do not match the commitment.
+		InvalidReveal,
+		/// The account is already in [`Feeders`].
+		AlreadyFeeder,
+		/// The account is not in [`Feeders`].
+		NotFeeder,
+		/// [`Feeders`] already has [`Config::MaxFeeders`] accounts.
+		TooManyFeeders,
+		/// [`Config::MaxProposals`] proposals are already open for votes.
+		TooManyProposals,
+		/// No proposal with this index is open for votes.
+		UnknownProposal,
+		/// The caller already voted on the proposal.
+		AlreadyVoted,
+		/// [`Config::MaxVoters`] accounts already voted on the proposal.
+		TooManyVoters,
+		/// The vote is for no balance.
+		NoVotingBalance,
+		/// The caller can't freeze the balance voted with and keep [`Config::VoteFeeMargin`] for
+		/// fees.
+		InsufficientVotingBalance,
+		/// [`ProposalCount`] has no index left for another proposal.
+		ProposalCountOverflow,
+		/// A batch sets the value of an account other than the caller.
+		NotOwnValue,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config<I>, I: 'static> Hooks<BlockNumberFor<T>> for Pallet<T, I> {
+		/// Apply the value changes scheduled for this block, close the votes ending with it, and
+		/// account for closing the oracle round in `on_finalize`.
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			let mut weight = Self::apply_scheduled(now);
+			weight.saturating_accrue(Self::close_proposals(now));
+			if Self::closes_round(now) {
+				weight.saturating_accrue(T::WeightInfo::close_round(
+					T::MaxFeeders::get(),
+					T::MaxHistory::get(),
+				));
+			}
+			weight
+		}
+
+		/// Close the oracle round if it ends with this block.
+		///
+		/// This runs in `on_finalize` rather than `on_initialize` so that the round is stamped
+		/// with this block's time, which is only set by the timestamp inherent.
+		fn on_finalize(now: BlockNumberFor<T>) {
+			if Self::closes_round(now) {
+				Self::close_round();
+			}
+		}
+
+		/// Remove expired values, then commitments whose reveal window has passed, as far as the
+		/// remaining weight allows.
+		fn on_idle(now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			let used = Self::expire_values(now, remaining_weight);
+			used.saturating_add(Self::expire_commitments(
+				now,
+				remaining_weight.saturating_sub(used),
+			))
+		}
+
+		/// Read a value and submit it back to the chain, see [`offchain`].
+		fn offchain_worker(block_number: BlockNumberFor<T>) {
+			if let Err(e) = Self::offchain_submit(block_number) {
+				log::debug!(target: LOG_TARGET, "offchain worker did not submit: {}", e);
+			}
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn try_state(_n: BlockNumberFor<T>) -> Result<(), TryRuntimeError> {
+			Self::do_try_state()
+		}
+
+		fn integrity_test() {
+			// Applying a full block of changes, closing every proposal with as many voters as
+			// possible and closing a full round must fit in a block.
+			let history = T::MaxHistory::get();
+			let max_block = T::BlockWeights::get().max_block;
+			assert!(T::WeightInfo::on_initialize_apply(T::MaxScheduledPerBlock::get(), history)
+				.saturating_add(
+					T::WeightInfo::close_proposal(T::MaxVoters::get(), history)
+						.saturating_mul(T::MaxProposals::get().into())
+				)
+				.saturating_add(T::WeightInfo::close_round(T::MaxFeeders::get(), history))
+				.all_lte(max_block));
+			// So must a full batch.
+			assert!(T::WeightInfo::set_many(T::MaxBatch::get(), history).all_lte(max_block));
+			assert!(!T::RoundLength::get().is_zero(), "oracle rounds must last at least one block");
+			assert!(
+				!T::FreeCallEra::get().is_zero(),
+				"free call eras must last at least one block"
+			);
+			assert!(
+				!T::VotingPeriod::get().is_zero(),
+				"votes on proposals must last at least one block"
+			);
+		}
 	}
 
 	/// The pallet's dispatchable functions ([`Call`]s).
@@ -146,23 +871,23 @@ pub mod pallet {
 	///
 	/// The [`weight`] macro is used to assign a weight to each call.
 	#[pallet::call]
-	impl<T: Config> Pallet<T> {
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
 		/// An example dispatchable that takes a single u32 value as a parameter, writes the value
-		/// to storage and emits an event.
+		/// to the caller's entry in storage and emits an event.
 		///
-		/// It checks that the _origin_ for this call is _Signed_ and returns a dispatch
-		/// error if it isn't. Learn more about origins here: <https://docs.substrate.io/build/origins/>
+		/// It checks that the _origin_ for this call is accepted by [`Config::SetOrigin`] and
+		/// returns a dispatch error if it isn't. Learn more about origins here: <https://docs.substrate.io/build/origins/>
 		#[pallet::call_index(0)]
-		#[pallet::weight(T::WeightInfo::do_something())]
+		#[pallet::weight(T::WeightInfo::do_something(T::MaxHistory::get()))]
 		pub fn do_something(origin: OriginFor<T>, something: u32) -> DispatchResult {
-			// Check that the extrinsic was signed and get the signer.
-			let who = ensure_signed(origin)?;
+			// Check that the origin may write and get the account to write for.
+			let who = T::SetOrigin::ensure_origin(origin)?;
 
-			// Update storage.
-			Something::<T>::put(something);
+			// Update storage, keeping hold of the value it replaces.
+			let old = Self::set_value(&who, something)?;
 
 			// Emit an event.
-			Self::deposit_event(Event::SomethingStored { something, who });
+			Self::deposit_event(Event::SomethingStored { who, old, new: something });
 
 			// Return a successful `DispatchResult`
 			Ok(())
@@ -170,35 +895,1227 @@ pub mod pallet {
 
 		/// An example dispatchable that may throw a custom error.
 		///
-		/// It checks that the caller is a signed origin and reads the current value from the
-		/// `Something` storage item. If a current value exists, it is incremented by 1 and then
-		/// written back to storage.
+		/// It checks that the caller is accepted by [`Config::SetOrigin`] and reads the caller's
+		/// current value from the `Something` storage map. If a current value exists, it is
+		/// incremented by 1 and then written back to storage.
 		///
 		/// ## Errors
 		///
 		/// The function will return an error under the following conditions:
 		///
-		/// - If no value has been set ([`Error::NoneValue`])
+		/// - If the caller has not set a value ([`Error::NoneValue`])
 		/// - If incrementing the value in storage causes an arithmetic overflow
 		///   ([`Error::StorageOverflow`])
 		#[pallet::call_index(1)]
-		#[pallet::weight(T::WeightInfo::cause_error())]
+		#[pallet::weight(T::WeightInfo::cause_error(T::MaxHistory::get()))]
 		pub fn cause_error(origin: OriginFor<T>) -> DispatchResult {
-			let _who = ensure_signed(origin)?;
+			let who = T::SetOrigin::ensure_origin(origin)?;
 
 			// Read a value from storage.
-			match Pallet::<T>::something() {
+			match Pallet::<T, I>::something(&who) {
 				// Return an error if the value has not been set.
-				None => Err(Error::<T>::NoneValue.into()),
+				None => Err(Error::<T, I>::NoneValue.into()),
 				Some(old) => {
 					// Increment the value read from storage. This will cause an error in the event
 					// of overflow.
-					let new = old.checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
+					let new = old.checked_add(1).ok_or(Error::<T, I>::StorageOverflow)?;
 					// Update the value in storage with the incremented result.
-					Something::<T>::put(new);
+					Something::<T, I>::insert(&who, new);
+					Self::record_history(&who, new);
+					Self::deposit_event(Event::SomethingStored { who, old: Some(old), new });
+					Ok(())
+				},
+			}
+		}
+
+		/// Remove the caller's value from storage, release its deposit and emit an event.
+		///
+		/// Any signed origin may clear its own value, even if [`Config::SetOrigin`] no longer
+		/// accepts it.
+		///
+		/// ## Errors
+		///
+		/// - If the caller has not set a value ([`Error::NoneValue`])
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::WeightInfo::clear_something())]
+		pub fn clear_something(origin: OriginFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let old = Something::<T, I>::take(&who).ok_or(Error::<T, I>::NoneValue)?;
+			Self::clear_expiry(&who);
+			Self::release_deposit(&who)?;
+			Self::deposit_event(Event::SomethingCleared { who, old });
+
+			Ok(())
+		}
+
+		/// Add an account to [`AllowedWriters`]. Root only.
+		#[pallet::call_index(3)]
+		#[pallet::weight(T::WeightInfo::add_writer())]
+		pub fn add_writer(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			ensure_root(origin)?;
+
+			ensure!(!AllowedWriters::<T, I>::contains_key(&who), Error::<T, I>::AlreadyWriter);
+			AllowedWriters::<T, I>::insert(&who, ());
+			Self::deposit_event(Event::WriterAdded { who });
+
+			Ok(())
+		}
+
+		/// Remove an account from [`AllowedWriters`]. Root only.
+		#[pallet::call_index(4)]
+		#[pallet::weight(T::WeightInfo::remove_writer())]
+		pub fn remove_writer(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			ensure_root(origin)?;
+
+			AllowedWriters::<T, I>::take(&who).ok_or(Error::<T, I>::NotWriter)?;
+			Self::deposit_event(Event::WriterRemoved { who });
+
+			Ok(())
+		}
+
+		/// Schedule the caller's value to be set to `value` at the start of block `at`.
+		///
+		/// The change is applied like [`Pallet::do_something`], so it fails if the caller has no
+		/// value by then and can't pay the deposit.
+		///
+		/// ## Errors
+		///
+		/// - If `at` is not in the future ([`Error::ScheduleInPast`])
+		/// - If the caller already has a change scheduled for `at` ([`Error::AlreadyScheduled`])
+		/// - If `at` already has [`Config::MaxScheduledPerBlock`] changes
+		///   ([`Error::TooManyScheduled`])
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::WeightInfo::schedule_set())]
+		pub fn schedule_set(
+			origin: OriginFor<T>,
+			value: u32,
+			at: BlockNumberFor<T>,
+		) -> DispatchResult {
+			let who = T::SetOrigin::ensure_origin(origin)?;
+			ensure!(at > frame_system::Pallet::<T>::block_number(), Error::<T, I>::ScheduleInPast);
+
+			Scheduled::<T, I>::try_mutate(at, |scheduled| {
+				ensure!(
+					!scheduled.iter().any(|change| change.who == who),
+					Error::<T, I>::AlreadyScheduled
+				);
+				scheduled
+					.try_push(ScheduledChange { who: who.clone(), value })
+					.map_err(|_| Error::<T, I>::TooManyScheduled)
+			})?;
+			Self::deposit_event(Event::ValueScheduled { who, value, at });
+
+			Ok(())
+		}
+
+		/// Cancel the change the caller scheduled for block `at`.
+		///
+		/// ## Errors
+		///
+		/// - If the caller has no change scheduled for `at` ([`Error::NotScheduled`])
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::WeightInfo::cancel_scheduled())]
+		pub fn cancel_scheduled(origin: OriginFor<T>, at: BlockNumberFor<T>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Scheduled::<T, I>::try_mutate_exists(at, |maybe_scheduled| {
+				let scheduled = maybe_scheduled.as_mut().ok_or(Error::<T, I>::NotScheduled)?;
+				let index = scheduled
+					.iter()
+					.position(|change| change.who == who)
+					.ok_or(Error::<T, I>::NotScheduled)?;
+				scheduled.remove(index);
+				if scheduled.is_empty() {
+					*maybe_scheduled = None;
+				}
+				Ok::<_, Error<T, I>>(())
+			})?;
+			Self::deposit_event(Event::ScheduleCancelled { who, at });
+
+			Ok(())
+		}
+
+		/// Store the value of a payload signed by the offchain worker.
+		///
+		/// The value is stored for the account of the payload's signer, which must be accepted by
+		/// [`Config::SetOrigin`] as if it had signed a [`Pallet::do_something`]. As the
+		/// transaction pays no fees, the signer must also be in [`AllowedWriters`]. The signature
+		/// is checked when the transaction is validated, see [`Pallet::validate_unsigned`].
+		///
+		/// ## Errors
+		///
+		/// - If the signer is not in [`AllowedWriters`] ([`Error::NotWriter`])
+		#[pallet::call_index(7)]
+		#[pallet::weight(T::WeightInfo::submit_value_unsigned(T::MaxHistory::get()))]
+		pub fn submit_value_unsigned(
+			origin: OriginFor<T>,
+			payload: ValuePayload<T::Public, BlockNumberFor<T>>,
+			_signature: T::Signature,
+		) -> DispatchResult {
+			ensure_none(origin)?;
+
+			let signer = RawOrigin::Signed(payload.public.into_account());
+			let who = T::SetOrigin::ensure_origin(signer.into())?;
+			ensure!(AllowedWriters::<T, I>::contains_key(&who), Error::<T, I>::NotWriter);
+			let old = Self::set_value(&who, payload.value)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			NextUnsignedAt::<T, I>::put(now.saturating_add(T::UnsignedInterval::get()));
+			Self::deposit_event(Event::SomethingStored { who, old, new: payload.value });
+
+			Ok(())
+		}
+
+		/// Create a counter called `name` starting at `value`, owned by the caller, holding
+		/// [`Config::CounterDeposit`] from the caller until the counter is deleted with
+		/// [`Pallet::delete_counter`].
+		///
+		/// `min` and `max` optionally bound the values the counter may take.
+		///
+		/// ## Errors
+		///
+		/// - If a counter called `name` exists ([`Error::CounterExists`])
+		/// - If `value` is outside of `min` and `max` ([`Error::OutOfBounds`])
+		/// - If the caller can't pay the deposit
+		#[pallet::call_index(8)]
+		#[pallet::weight(T::WeightInfo::create_counter(name.len() as u32))]
+		pub fn create_counter(
+			origin: OriginFor<T>,
+			name: CounterNameOf<T, I>,
+			value: u64,
+			min: Option<u64>,
+			max: Option<u64>,
+		) -> DispatchResult {
+			let owner = T::SetOrigin::ensure_origin(origin)?;
+
+			ensure!(!Counters::<T, I>::contains_key(&name), Error::<T, I>::CounterExists);
+			let deposit = T::CounterDeposit::get();
+			let counter = CounterInfo { owner: owner.clone(), deposit, value, min, max };
+			ensure!(counter.allows(value), Error::<T, I>::OutOfBounds);
+			T::Currency::hold(&HoldReason::<I>::CounterDeposit.into(), &owner, deposit)?;
+			Counters::<T, I>::insert(&name, counter);
+			Self::deposit_event(Event::CounterCreated { name, owner, value });
+
+			Ok(())
+		}
+
+		/// Add `by` to the caller's counter `name`.
+		///
+		/// ## Errors
+		///
+		/// - If the caller does not own the counter ([`Error::UnknownCounter`],
+		///   [`Error::NotCounterOwner`])
+		/// - If the addition overflows ([`Error::StorageOverflow`])
+		/// - If the result is above the counter's maximum ([`Error::OutOfBounds`])
+		#[pallet::call_index(9)]
+		#[pallet::weight(T::WeightInfo::increment(name.len() as u32))]
+		pub fn increment(
+			origin: OriginFor<T>,
+			name: CounterNameOf<T, I>,
+			by: u64,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::update_counter(&who, name, |counter| {
+				counter.value.checked_add(by).ok_or(Error::<T, I>::StorageOverflow)
+			})
+		}
+
+		/// Subtract `by` from the caller's counter `name`.
+		///
+		/// ## Errors
+		///
+		/// - If the caller does not own the counter ([`Error::UnknownCounter`],
+		///   [`Error::NotCounterOwner`])
+		/// - If the result would be negative ([`Error::Underflow`])
+		/// - If the result is below the counter's minimum ([`Error::OutOfBounds`])
+		#[pallet::call_index(10)]
+		#[pallet::weight(T::WeightInfo::decrement(name.len() as u32))]
+		pub fn decrement(
+			origin: OriginFor<T>,
+			name: CounterNameOf<T, I>,
+			by: u64,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::update_counter(&who, name, |counter| {
+				counter.value.checked_sub(by).ok_or(Error::<T, I>::Underflow)
+			})
+		}
+
+		/// Set the caller's counter `name` back to its minimum, or zero if it has none.
+		///
+		/// ## Errors
+		///
+		/// - If the caller does not own the counter ([`Error::UnknownCounter`],
+		///   [`Error::NotCounterOwner`])
+		#[pallet::call_index(11)]
+		#[pallet::weight(T::WeightInfo::reset(name.len() as u32))]
+		pub fn reset(origin: OriginFor<T>, name: CounterNameOf<T, I>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::update_counter(&who, name, |counter| Ok(counter.reset_value()))
+		}
+
+		/// Hand the caller's counter `name` to `new_owner`, along with its deposit, which is held
+		/// from `new_owner` from then on.
+		///
+		/// ## Errors
+		///
+		/// - If the caller does not own the counter ([`Error::UnknownCounter`],
+		///   [`Error::NotCounterOwner`])
+		/// - If the deposit can't be moved, for example because the caller's balance is frozen or
+		///   `new_owner` does not exist
+		#[pallet::call_index(12)]
+		#[pallet::weight(T::WeightInfo::transfer_ownership(name.len() as u32))]
+		pub fn transfer_ownership(
+			origin: OriginFor<T>,
+			name: CounterNameOf<T, I>,
+			new_owner: T::AccountId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Counters::<T, I>::try_mutate(&name, |maybe_counter| {
+				let counter = maybe_counter.as_mut().ok_or(Error::<T, I>::UnknownCounter)?;
+				ensure!(counter.owner == who, Error::<T, I>::NotCounterOwner);
+				T::Currency::transfer_on_hold(
+					&HoldReason::<I>::CounterDeposit.into(),
+					&who,
+					&new_owner,
+					counter.deposit,
+					Precision::Exact,
+					Restriction::OnHold,
+					Fortitude::Polite,
+				)?;
+				counter.owner = new_owner.clone();
+				Ok::<_, DispatchError>(())
+			})?;
+			Self::deposit_event(Event::CounterOwnershipTransferred {
+				name,
+				from: who,
+				to: new_owner,
+			});
+
+			Ok(())
+		}
+
+		/// Commit to a value without revealing it, as `hash` of the value and a salt.
+		///
+		/// The value is applied by [`Pallet::reveal`] in one of the following
+		/// [`Config::RevealWindow`] blocks. Compute `hash` with [`Pallet::commitment_hash`].
+		///
+		/// ## Errors
+		///
+		/// - If the caller has a commitment that can still be revealed
+		///   ([`Error::AlreadyCommitted`])
+		#[pallet::call_index(13)]
+		#[pallet::weight(T::WeightInfo::commit())]
+		pub fn commit(origin: OriginFor<T>, hash: [u8; 32]) -> DispatchResult {
+			let who = T::SetOrigin::ensure_origin(origin)?;
+			let now = frame_system::Pallet::<T>::block_number();
+
+			if let Some(existing) = Commitments::<T, I>::get(&who) {
+				ensure!(Self::reveal_by(&existing) < now, Error::<T, I>::AlreadyCommitted);
+			}
+			let commitment = Commitment { hash, committed_at: now };
+			let reveal_by = Self::reveal_by(&commitment);
+			Commitments::<T, I>::insert(&who, commitment);
+			Self::deposit_event(Event::Committed { who, reveal_by });
+
+			Ok(())
+		}
+
+		/// Reveal the value the caller committed to and store it like
+		/// [`Pallet::do_something`].
+		///
+		/// ## Errors
+		///
+		/// - If the caller has no commitment ([`Error::NoCommitment`])
+		/// - If the commitment was made in this block ([`Error::RevealTooEarly`])
+		/// - If the reveal window has passed ([`Error::RevealTooLate`])
+		/// - If `value` and `salt` don't match the commitment ([`Error::InvalidReveal`])
+		#[pallet::call_index(14)]
+		#[pallet::weight(T::WeightInfo::reveal(T::MaxHistory::get()))]
+		pub fn reveal(origin: OriginFor<T>, value: u32, salt: [u8; 32]) -> DispatchResult {
+			let who = T::SetOrigin::ensure_origin(origin)?;
+			let now = frame_system::Pallet::<T>::block_number();
+
+			let commitment = Commitments::<T, I>::get(&who).ok_or(Error::<T, I>::NoCommitment)?;
+			ensure!(commitment.committed_at < now, Error::<T, I>::RevealTooEarly);
+			ensure!(now <= Self::reveal_by(&commitment), Error::<T, I>::RevealTooLate);
+			ensure!(
+				commitment.hash == Self::commitment_hash(value, &salt),
+				Error::<T, I>::InvalidReveal
+			);
+
+			Commitments::<T, I>::remove(&who);
+			let old = Self::set_value(&who, value)?;
+			Self::deposit_event(Event::SomethingStored { who, old, new: value });
+
+			Ok(())
+		}
+
+		/// Add an account to [`Feeders`]. Root only.
+		///
+		/// ## Errors
+		///
+		/// - If the account is already a feeder ([`Error::AlreadyFeeder`])
+		/// - If there are [`Config::MaxFeeders`] feeders ([`Error::TooManyFeeders`])
+		#[pallet::call_index(15)]
+		#[pallet::weight(T::WeightInfo::add_feeder())]
+		pub fn add_feeder(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			ensure_root(origin)?;
+
+			Feeders::<T, I>::try_mutate(|feeders| {
+				let index =
+					feeders.binary_search(&who).err().ok_or(Error::<T, I>::AlreadyFeeder)?;
+				feeders.try_insert(index, who.clone()).map_err(|_| Error::<T, I>::TooManyFeeders)
+			})?;
+			Self::deposit_event(Event::FeederAdded { who });
+
+			Ok(())
+		}
+
+		/// Remove an account from [`Feeders`], dropping any value it fed in the current round.
+		/// Root only.
+		///
+		/// ## Errors
+		///
+		/// - If the account is not a feeder ([`Error::NotFeeder`])
+		#[pallet::call_index(16)]
+		#[pallet::weight(T::WeightInfo::remove_feeder())]
+		pub fn remove_feeder(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			ensure_root(origin)?;
+
+			Feeders::<T, I>::try_mutate(|feeders| {
+				let index = feeders.binary_search(&who).map_err(|_| Error::<T, I>::NotFeeder)?;
+				feeders.remove(index);
+				Ok::<_, Error<T, I>>(())
+			})?;
+			Feeds::<T, I>::mutate(|feeds| feeds.retain(|(feeder, _)| *feeder != who));
+			Self::deposit_event(Event::FeederRemoved { who });
+
+			Ok(())
+		}
+
+		/// Feed `value` to the current oracle round, replacing any value the caller fed before
+		/// in this round.
+		///
+		/// ## Errors
+		///
+		/// - If the caller is not a feeder ([`Error::NotFeeder`])
+		#[pallet::call_index(17)]
+		#[pallet::weight(T::WeightInfo::feed())]
+		pub fn feed(origin: OriginFor<T>, value: u32) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Feeders::<T, I>::get().binary_search(&who).is_ok(), Error::<T, I>::NotFeeder);
+
+			Feeds::<T, I>::try_mutate(|feeds| {
+				match feeds.iter_mut().find(|(feeder, _)| *feeder == who) {
+					Some((_, fed)) => *fed = value,
+					// Every feeder fits, so this only fails if the feeders changed mid-round.
+					None => feeds
+						.try_push((who.clone(), value))
+						.map_err(|_| Error::<T, I>::TooManyFeeders)?,
+				}
+				Ok::<_, Error<T, I>>(())
+			})?;
+			Self::deposit_event(Event::Fed { who, round: Round::<T, I>::get(), value });
+
+			Ok(())
+		}
+
+		/// Like [`Pallet::do_something`], but the value is removed at the end of the block
+		/// `ttl` blocks from now, releasing its deposit.
+		///
+		/// Expired values are removed in `on_idle`, so a value may outlive its time-to-live in
+		/// busy blocks. Writing the caller's value again replaces its time-to-live, or drops it
+		/// when written without one.
+		///
+		/// ## Errors
+		///
+		/// - If `ttl` is zero ([`Error::ZeroTtl`])
+		/// - If the expiry block already has [`Config::MaxExpiringPerBlock`] values expiring in
+		///   it ([`Error::TooManyExpiring`])
+		#[pallet::call_index(18)]
+		#[pallet::weight(T::WeightInfo::do_something_with_ttl(T::MaxHistory::get()))]
+		pub fn do_something_with_ttl(
+			origin: OriginFor<T>,
+			something: u32,
+			ttl: BlockNumberFor<T>,
+		) -> DispatchResult {
+			let who = T::SetOrigin::ensure_origin(origin)?;
+			ensure!(!ttl.is_zero(), Error::<T, I>::ZeroTtl);
+
+			let old = Self::set_value(&who, something)?;
+			let expires_at = frame_system::Pallet::<T>::block_number().saturating_add(ttl);
+			Expiring::<T, I>::try_append(expires_at, &who)
+				.map_err(|_| Error::<T, I>::TooManyExpiring)?;
+			ExpiresAt::<T, I>::insert(&who, expires_at);
+			ExpiryCursor::<T, I>::mutate(|cursor| {
+				*cursor = Some(cursor.map_or(expires_at, |cursor| cursor.min(expires_at)))
+			});
+			Self::deposit_event(Event::SomethingStored { who, old, new: something });
+
+			Ok(())
+		}
+
+		/// Set the caller's value to each value of a batch in turn, in one call.
+		///
+		/// The origin has to be accepted by [`Config::SetOrigin`], and every value of the batch
+		/// has to be keyed by the account it writes for. Each value is set like
+		/// [`Pallet::do_something`] sets it, holding the deposit from the caller if it has no
+		/// value yet. `mode` decides what happens to the rest of the batch when a value can't be
+		/// set, see [`BatchMode`].
+		///
+		/// ## Errors
+		///
+		/// - If a value is keyed by another account ([`Error::NotOwnValue`])
+		#[pallet::call_index(19)]
+		#[pallet::weight(T::WeightInfo::set_many(values.len() as u32, T::MaxHistory::get()))]
+		pub fn set_many(
+			origin: OriginFor<T>,
+			values: BatchOf<T, I>,
+			mode: BatchMode,
+		) -> DispatchResult {
+			let caller = T::SetOrigin::ensure_origin(origin)?;
+			let set = |who: &T::AccountId, value| -> Result<Option<u32>, DispatchError> {
+				ensure!(*who == caller, Error::<T, I>::NotOwnValue);
+				Self::set_value(who, value)
+			};
+
+			let values = values.into_iter().zip(0u32..);
+			match mode {
+				BatchMode::Atomic => with_transaction(|| {
+					for ((who, value), index) in values {
+						match set(&who, value) {
+							Ok(old) => Self::deposit_event(Event::BatchItemSucceeded {
+								index,
+								who,
+								old,
+								new: value,
+							}),
+							Err(error) => return TransactionOutcome::Rollback(Err(error)),
+						}
+					}
+					TransactionOutcome::Commit(Ok(()))
+				}),
+				BatchMode::BestEffort => {
+					for ((who, value), index) in values {
+						// Undo whatever a failed value wrote before failing.
+						let result = with_transaction(|| match set(&who, value) {
+							Ok(old) => TransactionOutcome::Commit(Ok(old)),
+							Err(error) => TransactionOutcome::Rollback(Err(error)),
+						});
+						match result {
+							Ok(old) => Self::deposit_event(Event::BatchItemSucceeded {
+								index,
+								who,
+								old,
+								new: value,
+							}),
+							Err(error) => Self::deposit_event(Event::BatchItemFailed {
+								index,
+								who,
+								value,
+								error,
+							}),
+						}
+					}
 					Ok(())
 				},
 			}
 		}
+
+		/// Propose storing `value` under [`Config::ProposalAccount`], holding
+		/// [`Config::ProposalDeposit`] from the caller until the vote on it ends.
+		///
+		/// Any account can [`Pallet::vote`] on the proposal for [`Config::VotingPeriod`] blocks.
+		/// It passes if the balance voting for it reaches [`Config::ApprovalThreshold`] of the
+		/// balance voting on it, and its value is then stored at the start of the block the vote
+		/// ends in.
+		#[pallet::call_index(20)]
+		#[pallet::weight(T::WeightInfo::propose())]
+		pub fn propose(origin: OriginFor<T>, value: u32) -> DispatchResult {
+			let proposer = ensure_signed(origin)?;
+			ensure!(
+				Proposals::<T, I>::count() < T::MaxProposals::get(),
+				Error::<T, I>::TooManyProposals
+			);
+
+			let index = ProposalCount::<T, I>::get();
+			let next = index.checked_add(1).ok_or(Error::<T, I>::ProposalCountOverflow)?;
+
+			let deposit = T::ProposalDeposit::get();
+			T::Currency::hold(&HoldReason::<I>::ProposalDeposit.into(), &proposer, deposit)?;
+			ProposalCount::<T, I>::put(next);
+			let end =
+				frame_system::Pallet::<T>::block_number().saturating_add(T::VotingPeriod::get());
+			ProposalsEnding::<T, I>::try_append(end, index)
+				.map_err(|_| Error::<T, I>::TooManyProposals)?;
+			let proposal = Proposal {
+				proposer: proposer.clone(),
+				value,
+				deposit,
+				end,
+				ayes: Zero::zero(),
+				nays: Zero::zero(),
+				voters: 0,
+			};
+			Proposals::<T, I>::insert(index, proposal);
+			Self::deposit_event(Event::Proposed { index, proposer, value, end });
+
+			Ok(())
+		}
+
+		/// Vote for (`aye`) or against proposal `index` with `balance` of the caller's free
+		/// balance.
+		///
+		/// The balance is frozen until the votes on all proposals the caller voted on have ended.
+		/// It can be at most the caller's free balance less the existential deposit and
+		/// [`Config::VoteFeeMargin`], which stay spendable to pay fees. Each account can vote once
+		/// on each proposal.
+		#[pallet::call_index(21)]
+		#[pallet::weight(T::WeightInfo::vote())]
+		pub fn vote(
+			origin: OriginFor<T>,
+			index: ProposalIndex,
+			aye: bool,
+			balance: BalanceOf<T, I>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let mut proposal =
+				Proposals::<T, I>::get(index).ok_or(Error::<T, I>::UnknownProposal)?;
+			ensure!(!Votes::<T, I>::contains_key(index, &who), Error::<T, I>::AlreadyVoted);
+			ensure!(proposal.voters < T::MaxVoters::get(), Error::<T, I>::TooManyVoters);
+			ensure!(!balance.is_zero(), Error::<T, I>::NoVotingBalance);
+			// Freezes overlap, so the caller's other votes don't count against the balance.
+			let votable =
+				T::Currency::reducible_balance(&who, Preservation::Preserve, Fortitude::Force)
+					.saturating_sub(T::VoteFeeMargin::get());
+			ensure!(balance <= votable, Error::<T, I>::InsufficientVotingBalance);
+
+			VoteLocks::<T, I>::try_append(&who, (index, balance))
+				.map_err(|_| Error::<T, I>::TooManyProposals)?;
+			T::Currency::extend_freeze(&FreezeReason::<I>::Vote.into(), &who, balance)?;
+			if aye {
+				proposal.ayes.saturating_accrue(balance);
+			} else {
+				proposal.nays.saturating_accrue(balance);
+			}
+			proposal.voters += 1;
+			let (ayes, nays) = (proposal.ayes, proposal.nays);
+			Proposals::<T, I>::insert(index, proposal);
+			Votes::<T, I>::insert(index, &who, Vote { aye, balance });
+			Self::deposit_event(Event::Voted { index, who, aye, balance, ayes, nays });
+
+			Ok(())
+		}
+
+		/// Delete the caller's counter `name` and release its deposit.
+		///
+		/// ## Errors
+		///
+		/// - If the caller does not own the counter ([`Error::UnknownCounter`],
+		///   [`Error::NotCounterOwner`])
+		#[pallet::call_index(22)]
+		#[pallet::weight(T::WeightInfo::delete_counter(name.len() as u32))]
+		pub fn delete_counter(origin: OriginFor<T>, name: CounterNameOf<T, I>) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let counter = Counters::<T, I>::get(&name).ok_or(Error::<T, I>::UnknownCounter)?;
+			ensure!(counter.owner == who, Error::<T, I>::NotCounterOwner);
+			Counters::<T, I>::remove(&name);
+			let reason = HoldReason::<I>::CounterDeposit.into();
+			T::Currency::release(&reason, &who, counter.deposit, Precision::BestEffort)?;
+			Self::deposit_event(Event::CounterDeleted { name, owner: who });
+
+			Ok(())
+		}
+	}
+
+	#[pallet::validate_unsigned]
+	impl<T: Config<I>, I: 'static> ValidateUnsigned for Pallet<T, I> {
+		type Call = Call<T, I>;
+
+		/// Accept [`Call::submit_value_unsigned`] if [`NextUnsignedAt`] has been reached and its
+		/// payload is correctly signed by an account in [`AllowedWriters`] that
+		/// [`Config::SetOrigin`] accepts and that can pay for the value it stores.
+		///
+		/// Unsigned transactions pay no fees, so everything the call checks is checked here
+		/// already: a transaction that fails in the block would still be included for free.
+		fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+			let Call::submit_value_unsigned { payload, signature } = call else {
+				return InvalidTransaction::Call.into()
+			};
+
+			if !SignedPayload::<T>::verify::<T::AuthorityId>(payload, signature.clone()) {
+				return InvalidTransaction::BadProof.into()
+			}
+			let next = NextUnsignedAt::<T, I>::get();
+			if payload.block_number < next {
+				return InvalidTransaction::Stale.into()
+			}
+			if payload.block_number > frame_system::Pallet::<T>::block_number() {
+				return InvalidTransaction::Future.into()
+			}
+			let signer = RawOrigin::Signed(payload.public.clone().into_account());
+			let Ok(who) = T::SetOrigin::try_origin(signer.into()) else {
+				return InvalidTransaction::BadSigner.into()
+			};
+			if !AllowedWriters::<T, I>::contains_key(&who) {
+				return InvalidTransaction::BadSigner.into()
+			}
+			if !Something::<T, I>::contains_key(&who) &&
+				T::Currency::ensure_can_hold(
+					&HoldReason::<I>::ValueDeposit.into(),
+					&who,
+					T::ValueDeposit::get(),
+				)
+				.is_err()
+			{
+				return InvalidTransaction::Payment.into()
+			}
+
+			ValidTransaction::with_tag_prefix("TemplateOffchainWorker")
+				.priority(T::UnsignedPriority::get())
+				.and_provides((<Self as PalletInfoAccess>::index(), next))
+				.longevity(5)
+				.propagate(true)
+				.build()
+		}
+	}
+
+	impl<T: Config<I>, I: 'static> Pallet<T, I> {
+		/// Store `value` for `who` and record it in [`History`], returning the value it replaces.
+		///
+		/// The first value an account stores has to be paid for with [`Config::ValueDeposit`].
+		/// Nothing is written if the deposit can't be held. A value replacing one with a
+		/// time-to-live does not expire.
+		pub(crate) fn set_value(
+			who: &T::AccountId,
+			value: u32,
+		) -> Result<Option<u32>, DispatchError> {
+			let old = Something::<T, I>::get(who);
+			if old.is_none() {
+				T::Currency::hold(
+					&HoldReason::<I>::ValueDeposit.into(),
+					who,
+					T::ValueDeposit::get(),
+				)?;
+			} else {
+				Self::clear_expiry(who);
+			}
+			Something::<T, I>::insert(who, value);
+			Self::record_history(who, value);
+			Ok(old)
+		}
+
+		/// Store `value` for an account the pallet writes for itself, [`Config::OracleAccount`]
+		/// or [`Config::ProposalAccount`], and record it in [`History`].
+		///
+		/// No deposit is held. A time-to-live the account's previous value had is dropped, so that
+		/// it does not remove the pallet's value.
+		fn set_system_value(who: &T::AccountId, value: u32) {
+			Self::clear_expiry(who);
+			Something::<T, I>::insert(who, value);
+			Self::record_history(who, value);
+		}
+
+		/// Set the counter `name` owned by `who` to the value `f` computes from it, if that value
+		/// is within the counter's bounds.
+		fn update_counter(
+			who: &T::AccountId,
+			name: CounterNameOf<T, I>,
+			f: impl FnOnce(&CounterInfoOf<T, I>) -> Result<u64, Error<T, I>>,
+		) -> DispatchResult {
+			let (old, new) = Counters::<T, I>::try_mutate(&name, |maybe_counter| {
+				let counter = maybe_counter.as_mut().ok_or(Error::<T, I>::UnknownCounter)?;
+				ensure!(&counter.owner == who, Error::<T, I>::NotCounterOwner);
+				let new = f(counter)?;
+				ensure!(counter.allows(new), Error::<T, I>::OutOfBounds);
+				Ok::<_, Error<T, I>>((core::mem::replace(&mut counter.value, new), new))
+			})?;
+			Self::deposit_event(Event::CounterUpdated { name, old, new });
+
+			Ok(())
+		}
+
+		/// Apply the value changes scheduled for `now`.
+		fn apply_scheduled(now: BlockNumberFor<T>) -> Weight {
+			let due = Scheduled::<T, I>::take(now);
+			if due.is_empty() {
+				return T::WeightInfo::on_initialize_idle()
+			}
+
+			let count = due.len() as u32;
+			for ScheduledChange { who, value } in due {
+				match Self::set_value(&who, value) {
+					Ok(old) =>
+						Self::deposit_event(Event::ScheduledValueApplied { who, old, new: value }),
+					Err(error) =>
+						Self::deposit_event(Event::ScheduledValueFailed { who, value, error }),
+				}
+			}
+			T::WeightInfo::on_initialize_apply(count, T::MaxHistory::get())
+		}
+
+		/// Close the votes on the proposals ending at the start of block `now`.
+		fn close_proposals(now: BlockNumberFor<T>) -> Weight {
+			let mut weight = T::DbWeight::get().reads_writes(1, 1);
+			for index in ProposalsEnding::<T, I>::take(now) {
+				let voters = Self::close_proposal(index);
+				weight
+					.saturating_accrue(T::WeightInfo::close_proposal(voters, T::MaxHistory::get()));
+			}
+			weight
+		}
+
+		/// Close the vote on proposal `index`, storing its value if it passed, then release the
+		/// proposer's deposit and the voters' freezes. Returns the number of voters.
+		///
+		/// Like the oracle's, the value is written without a deposit and recorded in [`History`].
+		pub(crate) fn close_proposal(index: ProposalIndex) -> u32 {
+			let Some(proposal) = Proposals::<T, I>::take(index) else { return 0 };
+			let Proposal { proposer, value, deposit, ayes, nays, voters, .. } = proposal;
+
+			let threshold = T::ApprovalThreshold::get().mul_ceil(ayes.saturating_add(nays));
+			if !ayes.is_zero() && ayes >= threshold {
+				Self::set_system_value(&T::ProposalAccount::get(), value);
+				Self::deposit_event(Event::ProposalPassed { index, value, ayes, nays });
+			} else {
+				Self::deposit_event(Event::ProposalRejected { index, ayes, nays });
+			}
+
+			let reason = HoldReason::<I>::ProposalDeposit.into();
+			let released = T::Currency::release(&reason, &proposer, deposit, Precision::BestEffort);
+			if let Err(e) = released {
+				log::warn!(target: LOG_TARGET, "failed to release deposit of {:?}: {:?}", proposer, e);
+			}
+			for (who, _) in Votes::<T, I>::drain_prefix(index) {
+				Self::unlock_vote(&who, index);
+			}
+			voters
+		}
+
+		/// Drop proposal `index` from the votes `who` is locked for, and shrink its freeze to the
+		/// largest balance it voted with on the remaining ones.
+		fn unlock_vote(who: &T::AccountId, index: ProposalIndex) {
+			let mut locks = VoteLocks::<T, I>::take(who);
+			locks.retain(|(voted, _)| *voted != index);
+			let reason = FreezeReason::<I>::Vote.into();
+			let result = match locks.iter().map(|(_, balance)| *balance).max() {
+				Some(balance) => {
+					VoteLocks::<T, I>::insert(who, locks);
+					T::Currency::set_freeze(&reason, who, balance)
+				},
+				None => T::Currency::thaw(&reason, who),
+			};
+			if let Err(e) = result {
+				log::warn!(target: LOG_TARGET, "failed to unfreeze {:?}: {:?}", who, e);
+			}
+		}
+
+		/// Whether the oracle round ends with block `now`.
+		fn closes_round(now: BlockNumberFor<T>) -> bool {
+			let length = T::RoundLength::get();
+			!length.is_zero() && (now % length).is_zero()
+		}
+
+		/// Close the current oracle round, storing the median of its values under
+		/// [`Config::OracleAccount`], and start the next one.
+		///
+		/// The value is written without a deposit and recorded in [`History`] like any other.
+		pub(crate) fn close_round() {
+			let round = Round::<T, I>::get();
+			let mut values =
+				Feeds::<T, I>::take().into_iter().map(|(_, value)| value).collect::<Vec<_>>();
+			let fed = values.len() as u32;
+			let value = Self::median(&mut values);
+			if let Some(value) = value {
+				Self::set_system_value(&T::OracleAccount::get(), value);
+				Aggregated::<T, I>::put(AggregatedValue {
+					value,
+					round,
+					updated_at: T::Time::now(),
+				});
+			}
+			Round::<T, I>::put(round.wrapping_add(1));
+			let feeders = Feeders::<T, I>::decode_len().unwrap_or(0) as u32;
+			Self::deposit_event(Event::RoundFinalized { round, value, fed, feeders });
+		}
+
+		/// The median of `values`, or the mean of the two middle values, rounded down, if there
+		/// is an even number of them.
+		///
+		/// Unlike the mean, it can't be moved arbitrarily far by a minority of outliers.
+		fn median(values: &mut [u32]) -> Option<u32> {
+			values.sort_unstable();
+			let middle = values.len() / 2;
+			match values.len() {
+				0 => None,
+				len if len % 2 == 1 => Some(values[middle]),
+				_ => {
+					let (low, high) = (values[middle - 1], values[middle]);
+					Some(low + (high - low) / 2)
+				},
+			}
+		}
+
+		/// The last aggregated oracle value and its age in [`Config::Time`]'s unit.
+		pub fn aggregated() -> Option<(u32, MomentOf<T, I>)> {
+			Aggregated::<T, I>::get().map(|aggregated| {
+				(aggregated.value, T::Time::now().saturating_sub(aggregated.updated_at))
+			})
+		}
+
+		/// The hash to [`Pallet::commit`] to for revealing `value` with `salt`.
+		pub fn commitment_hash(value: u32, salt: &[u8; 32]) -> [u8; 32] {
+			(value, salt).using_encoded(sp_io::hashing::blake2_256)
+		}
+
+		/// The last block in which `commitment` can be revealed.
+		fn reveal_by(commitment: &CommitmentOf<T>) -> BlockNumberFor<T> {
+			commitment.committed_at.saturating_add(T::RevealWindow::get())
+		}
+
+		/// Remove the commitments that can no longer be revealed at `now`, using at most `limit`
+		/// weight.
+		///
+		/// Continues from [`CommitmentCursor`], so repeated calls eventually check every
+		/// commitment however little weight each one has.
+		pub(crate) fn expire_commitments(now: BlockNumberFor<T>, limit: Weight) -> Weight {
+			let mut used = T::DbWeight::get().reads_writes(1, 1);
+			let per_commitment = T::WeightInfo::expire_commitment();
+			if used.saturating_add(per_commitment).any_gt(limit) {
+				return Weight::zero()
+			}
+
+			let mut commitments = match CommitmentCursor::<T, I>::get() {
+				Some(last) =>
+					Commitments::<T, I>::iter_from(Commitments::<T, I>::hashed_key_for(last)),
+				None => Commitments::<T, I>::iter(),
+			};
+			let mut cursor = None;
+			while used.saturating_add(per_commitment).all_lte(limit) {
+				let Some((who, commitment)) = commitments.next() else {
+					cursor = None;
+					break
+				};
+				used.saturating_accrue(per_commitment);
+				if Self::reveal_by(&commitment) < now {
+					Commitments::<T, I>::remove(&who);
+					Self::deposit_event(Event::CommitmentExpired { who: who.clone() });
+				}
+				cursor = Some(who);
+			}
+			CommitmentCursor::<T, I>::set(cursor);
+
+			used
+		}
+
+		/// Drop the time-to-live of `who`'s value, if it has one.
+		fn clear_expiry(who: &T::AccountId) {
+			if let Some(at) = ExpiresAt::<T, I>::take(who) {
+				Expiring::<T, I>::mutate_exists(at, |maybe_expiring| {
+					if let Some(expiring) = maybe_expiring {
+						expiring.retain(|account| account != who);
+						if expiring.is_empty() {
+							*maybe_expiring = None;
+						}
+					}
+				});
+			}
+		}
+
+		/// Remove the values that expired at or before `now`, using at most `limit` weight.
+		///
+		/// Continues from [`ExpiryCursor`], so when more values expire at once than fit in one
+		/// block, the rest are removed in the following blocks.
+		pub(crate) fn expire_values(now: BlockNumberFor<T>, limit: Weight) -> Weight {
+			let mut used = T::DbWeight::get().reads_writes(1, 1);
+			if used.any_gt(limit) {
+				return Weight::zero()
+			}
+			let Some(mut block) = ExpiryCursor::<T, I>::get() else { return used };
+
+			let per_block = T::DbWeight::get().reads_writes(1, 1);
+			let per_value = T::WeightInfo::expire_value();
+			while block <= now && used.saturating_add(per_block).all_lte(limit) {
+				used.saturating_accrue(per_block);
+				let mut expiring = Expiring::<T, I>::get(block).into_inner();
+				while used.saturating_add(per_value).all_lte(limit) {
+					let Some(who) = expiring.pop() else { break };
+					used.saturating_accrue(per_value);
+					Self::expire_value(&who);
+				}
+				if !expiring.is_empty() {
+					// Out of weight, continue with the rest of this block next time.
+					Expiring::<T, I>::insert(block, BoundedVec::truncate_from(expiring));
+					break
+				}
+				Expiring::<T, I>::remove(block);
+				block.saturating_inc();
+			}
+			ExpiryCursor::<T, I>::put(block);
+
+			used
+		}
+
+		/// Remove the expired value of `who` and release its deposit.
+		pub(crate) fn expire_value(who: &T::AccountId) {
+			ExpiresAt::<T, I>::remove(who);
+			let Some(value) = Something::<T, I>::take(who) else { return };
+			if let Err(e) = Self::release_deposit(who) {
+				log::warn!(target: LOG_TARGET, "failed to release deposit of {:?}: {:?}", who, e);
+			}
+			Self::deposit_event(Event::ValueExpired { who: who.clone(), value });
+		}
+
+		/// Release everything held from `who` for [`HoldReason::ValueDeposit`].
+		///
+		/// Releases whatever is actually held rather than [`Config::ValueDeposit`], which may have
+		/// changed since the deposit was taken.
+		fn release_deposit(who: &T::AccountId) -> DispatchResult {
+			let reason = HoldReason::<I>::ValueDeposit.into();
+			let held = T::Currency::balance_on_hold(&reason, who);
+			T::Currency::release(&reason, who, held, Precision::BestEffort)?;
+			Ok(())
+		}
+
+		/// Append a newly written value to [`History`], dropping the oldest entry if it is full.
+		fn record_history(who: &T::AccountId, value: u32) {
+			let entry = HistoryEntry {
+				block: frame_system::Pallet::<T>::block_number(),
+				who: who.clone(),
+				value,
+			};
+			History::<T, I>::mutate(|history| {
+				if history.len() as u32 >= T::MaxHistory::get() && !history.is_empty() {
+					history.remove(0);
+				}
+				// Can only fail if `MaxHistory` is zero, in which case nothing is kept.
+				let _ = history.try_push(entry);
+			});
+		}
+
+		/// Check the pallet's storage invariants.
+		///
+		/// - [`History`] is within [`Config::MaxHistory`], ordered by block and not ahead of the
+		///   current block.
+		/// - Every account with a value in [`Something`] has a [`HoldReason::ValueDeposit`] hold,
+		///   except [`Config::OracleAccount`] and [`Config::ProposalAccount`], whose values the
+		///   pallet writes itself without a deposit.
+		/// - Accounts in [`History`] or [`Scheduled`] without a value have no deposit held.
+		/// - [`Scheduled`] only holds non-empty changes for future blocks, at most one per
+		///   account and block.
+		/// - Every account in [`ExpiresAt`] has a value and is listed once in [`Expiring`] under
+		///   its expiry block, and [`Expiring`] lists no other accounts.
+		/// - Every counter in [`Counters`] is within its bounds, and each owner has exactly the
+		///   deposits of its counters held for [`HoldReason::CounterDeposit`].
+		/// - No commitment in [`Commitments`] is from a future block.
+		/// - [`Feeders`] is sorted without duplicates and only feeders have fed the current
+		///   round, each at most once.
+		/// - [`Aggregated`] is from a past round.
+		/// - At most [`Config::MaxProposals`] proposals are open, each for a future block, indexed
+		///   in [`ProposalsEnding`] and tallying exactly its [`Votes`].
+		/// - Every vote is in its account's [`VoteLocks`], which lists no other proposals and
+		///   matches its [`FreezeReason::Vote`] freeze.
+		#[cfg(any(feature = "try-runtime", feature = "fuzzing", test))]
+		pub fn do_try_state() -> Result<(), TryRuntimeError> {
+			let now = frame_system::Pallet::<T>::block_number();
+			let reason: T::RuntimeHoldReason = HoldReason::<I>::ValueDeposit.into();
+			let check_deposit = |who: &T::AccountId| -> Result<(), TryRuntimeError> {
+				ensure!(
+					Something::<T, I>::contains_key(who) ||
+						T::Currency::balance_on_hold(&reason, who).is_zero(),
+					"account without a value has a deposit held"
+				);
+				Ok(())
+			};
+
+			let exempt = [T::OracleAccount::get(), T::ProposalAccount::get()];
+			for (who, _) in Something::<T, I>::iter() {
+				ensure!(
+					exempt.contains(&who) ||
+						T::ValueDeposit::get().is_zero() ||
+						!T::Currency::balance_on_hold(&reason, &who).is_zero(),
+					"account with a value has no deposit held"
+				);
+			}
+
+			let history = History::<T, I>::get();
+			ensure!(history.len() as u32 <= T::MaxHistory::get(), "history exceeds MaxHistory");
+			ensure!(
+				history.windows(2).all(|pair| pair[0].block <= pair[1].block),
+				"history is not ordered by block"
+			);
+			for entry in history.iter() {
+				ensure!(entry.block <= now, "history entry is from a future block");
+				check_deposit(&entry.who)?;
+			}
+
+			let mut expiring = 0usize;
+			for (at, accounts) in Expiring::<T, I>::iter() {
+				ensure!(!accounts.is_empty(), "empty expiry index entries are stored");
+				for who in accounts.iter() {
+					ensure!(
+						ExpiresAt::<T, I>::get(who) == Some(at),
+						"account listed under a block its value does not expire in"
+					);
+				}
+				expiring += accounts.len();
+			}
+			let mut expiry_count = 0usize;
+			for (who, _) in ExpiresAt::<T, I>::iter() {
+				ensure!(
+					Something::<T, I>::contains_key(&who),
+					"account without a value has an expiry"
+				);
+				expiry_count += 1;
+			}
+			ensure!(expiring == expiry_count, "expiry index does not match ExpiresAt");
+
+			let mut counter_deposits = BTreeMap::<T::AccountId, BalanceOf<T, I>>::new();
+			for (_, counter) in Counters::<T, I>::iter() {
+				ensure!(counter.allows(counter.value), "counter is outside of its bounds");
+				counter_deposits
+					.entry(counter.owner)
+					.or_default()
+					.saturating_accrue(counter.deposit);
+			}
+			let counter_reason: T::RuntimeHoldReason = HoldReason::<I>::CounterDeposit.into();
+			for (who, deposit) in counter_deposits {
+				ensure!(
+					T::Currency::balance_on_hold(&counter_reason, &who) == deposit,
+					"counter owner does not have its counters' deposits held"
+				);
+			}
+
+			for (_, commitment) in Commitments::<T, I>::iter() {
+				ensure!(commitment.committed_at <= now, "commitment is from a future block");
+			}
+
+			let feeders = Feeders::<T, I>::get();
+			ensure!(
+				feeders.windows(2).all(|pair| pair[0] < pair[1]),
+				"feeders are not sorted or contain duplicates"
+			);
+			let feeds = Feeds::<T, I>::get();
+			for (index, (who, _)) in feeds.iter().enumerate() {
+				ensure!(
+					feeders.binary_search(who).is_ok(),
+					"value fed by an account that is not a feeder"
+				);
+				ensure!(
+					!feeds[..index].iter().any(|(other, _)| other == who),
+					"feeder fed the current round more than once"
+				);
+			}
+			if let Some(aggregated) = Aggregated::<T, I>::get() {
+				ensure!(
+					aggregated.round < Round::<T, I>::get(),
+					"aggregated value is from a future round"
+				);
+			}
+
+			for (at, changes) in Scheduled::<T, I>::iter() {
+				ensure!(at > now, "change scheduled for a past block");
+				ensure!(!changes.is_empty(), "empty scheduled changes are stored");
+				ensure!(
+					changes.len() as u32 <= T::MaxScheduledPerBlock::get(),
+					"scheduled changes exceed MaxScheduledPerBlock"
+				);
+				for (index, change) in changes.iter().enumerate() {
+					ensure!(
+						!changes[..index].iter().any(|other| other.who == change.who),
+						"account has more than one change scheduled for a block"
+					);
+					check_deposit(&change.who)?;
+				}
+			}
+
+			let mut open = 0u32;
+			for (index, proposal) in Proposals::<T, I>::iter() {
+				ensure!(proposal.end > now, "vote on a proposal ended without closing it");
+				ensure!(
+					ProposalsEnding::<T, I>::get(proposal.end).contains(&index),
+					"proposal is not indexed under the block its vote ends in"
+				);
+				let (mut ayes, mut nays, mut voters) = (Zero::zero(), Zero::zero(), 0u32);
+				for (who, vote) in Votes::<T, I>::iter_prefix(index) {
+					ensure!(
+						VoteLocks::<T, I>::get(&who).contains(&(index, vote.balance)),
+						"vote is not locked"
+					);
+					if vote.aye {
+						ayes.saturating_accrue(vote.balance);
+					} else {
+						nays.saturating_accrue(vote.balance);
+					}
+					voters += 1;
+				}
+				ensure!(
+					(proposal.ayes, proposal.nays, proposal.voters) == (ayes, nays, voters),
+					"proposal tally does not match its votes"
+				);
+				open += 1;
+			}
+			ensure!(open == Proposals::<T, I>::count(), "proposal counter is off");
+			ensure!(open <= T::MaxProposals::get(), "more than MaxProposals proposals are open");
+			let freeze: T::RuntimeFreezeReason = FreezeReason::<I>::Vote.into();
+			for (who, locks) in VoteLocks::<T, I>::iter() {
+				for (index, _) in locks.iter() {
+					ensure!(Votes::<T, I>::contains_key(index, &who), "vote lock without a vote");
+				}
+				ensure!(
+					locks.iter().map(|(_, balance)| *balance).max() ==
+						Some(T::Currency::balance_frozen(&freeze, &who)),
+					"vote freeze does not match the locked votes"
+				);
+			}
+
+			Ok(())
+		}
+
+		/// The most recently written value.
+		pub fn latest() -> Option<HistoryEntryOf<T>> {
+			History::<T, I>::get().last().cloned()
+		}
+
+		/// Up to `limit` of the most recently written values, newest first.
+		pub fn history(limit: u32) -> Vec<HistoryEntryOf<T>> {
+			History::<T, I>::get().into_iter().rev().take(limit as usize).collect()
+		}
+
+		/// The last value written at or before `block`, if it is still in [`History`].
+		pub fn value_at(block: BlockNumberFor<T>) -> Option<HistoryEntryOf<T>> {
+			History::<T, I>::get().into_iter().rev().find(|entry| entry.block <= block)
+		}
+
+		/// The storage key of the value of `who` in [`Something`], to prove it against a block's
+		/// state root with `pallet_template_proof`.
+		pub fn value_key(who: &T::AccountId) -> Vec<u8> {
+			Something::<T, I>::hashed_key_for(who)
+		}
+	}
+}
+
+/// Accepts signed origins whose account is in [`AllowedWriters`].
+pub struct EnsureWriter<T, I = ()>(sp_std::marker::PhantomData<(T, I)>);
+
+impl<T: Config<I>, I: 'static> EnsureOrigin<T::RuntimeOrigin> for EnsureWriter<T, I> {
+	type Success = T::AccountId;
+
+	fn try_origin(o: T::RuntimeOrigin) -> Result<Self::Success, T::RuntimeOrigin> {
+		let raw: Result<RawOrigin<T::AccountId>, T::RuntimeOrigin> = o.into();
+		raw.and_then(|o| match o {
+			RawOrigin::Signed(who) if AllowedWriters::<T, I>::contains_key(&who) => Ok(who),
+			r => Err(T::RuntimeOrigin::from(r)),
+		})
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin() -> Result<T::RuntimeOrigin, ()> {
+		let who: T::AccountId = frame_benchmarking::whitelisted_caller();
+		AllowedWriters::<T, I>::insert(&who, ());
+		Ok(RawOrigin::Signed(who).into())
 	}
 }
@@ -0,0 +1,122 @@
+//! Storage migrations for the template pallet.
+//!
+//! Each storage layout change gets its own `vN` module holding an unchecked migration, which
+//! assumes it runs against the layout of version `N - 1`, and a [`VersionedMigration`] alias that
+//! only runs it when the on-chain storage version matches. Add the alias to the runtime's
+//! `Migrations` tuple.
+
+use super::*;
+#[cfg(feature = "try-runtime")]
+use frame_support::traits::fungible::InspectHold;
+use frame_support::{
+	migrations::VersionedMigration, pallet_prelude::*, storage_alias, traits::OnRuntimeUpgrade,
+};
+#[cfg(feature = "try-runtime")]
+use sp_runtime::{traits::Zero, TryRuntimeError};
+#[cfg(feature = "try-runtime")]
+use sp_std::vec::Vec;
+
+/// Migrates the single global `Something` value into the per-account `Something` map.
+pub mod v1 {
+	use super::*;
+
+	/// The `Something` storage item as it was declared before it became keyed by account.
+	#[storage_alias]
+	pub(crate) type Something<T: Config<I>, I: 'static> = StorageValue<Pallet<T, I>, u32>;
+
+	/// Moves the old global value under the account returned by `Owner`.
+	///
+	/// The value is stored like any other: [`Config::ValueDeposit`] is held from the owner and the
+	/// value is recorded in [`History`]. The old value is removed whether or not `Owner` returns
+	/// an account, and dropped if the owner can't pay the deposit. Use
+	/// [`MigrateToPerAccount`] rather than this type directly, so the storage version is checked
+	/// and bumped.
+	pub struct UncheckedMigrateToPerAccount<T, Owner, I = ()>(PhantomData<(T, Owner, I)>);
+
+	impl<T: Config<I>, Owner: Get<Option<T::AccountId>>, I: 'static> OnRuntimeUpgrade
+		for UncheckedMigrateToPerAccount<T, Owner, I>
+	{
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			let old = Something::<T, I>::get();
+			let owner = Owner::get();
+			if let Some(owner) = &owner {
+				ensure!(
+					crate::Something::<T, I>::get(owner).is_none() || old.is_none(),
+					"v1: owner already has a per-account value that would be overwritten"
+				);
+			}
+			Ok((old, owner).encode())
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			let Some(value) = Something::<T, I>::take() else { return T::DbWeight::get().reads(1) };
+
+			match Owner::get() {
+				Some(owner) => {
+					match Pallet::<T, I>::set_value(&owner, value) {
+						Ok(_) => log::info!(
+							target: LOG_TARGET,
+							"moved global value {} under account {:?}",
+							value,
+							owner
+						),
+						Err(e) => log::warn!(
+							target: LOG_TARGET,
+							"owner {:?} can't pay the deposit for global value {}, dropping it: {:?}",
+							owner,
+							value,
+							e
+						),
+					}
+					// The global value, the owner's value, its account and holds, and the history.
+					T::DbWeight::get().reads_writes(5, 5)
+				},
+				None => {
+					log::warn!(
+						target: LOG_TARGET,
+						"no owner for global value {}, dropping it",
+						value
+					);
+					T::DbWeight::get().reads_writes(2, 1)
+				},
+			}
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			let (old, owner) = <(Option<u32>, Option<T::AccountId>)>::decode(&mut &state[..])
+				.map_err(|_| "v1: failed to decode pre-upgrade state")?;
+			ensure!(!Something::<T, I>::exists(), "v1: global value was not removed");
+			if let (Some(old), Some(owner)) = (old, owner) {
+				// The value is dropped if the owner can't pay the deposit.
+				if let Some(value) = crate::Something::<T, I>::get(&owner) {
+					ensure!(value == old, "v1: global value was not moved under the owner");
+					ensure!(
+						!T::Currency::balance_on_hold(
+							&HoldReason::<I>::ValueDeposit.into(),
+							&owner
+						)
+						.is_zero(),
+						"v1: no deposit is held for the moved value"
+					);
+					ensure!(
+						History::<T, I>::get().last().map(|entry| (&entry.who, entry.value)) ==
+							Some((&owner, old)),
+						"v1: moved value is not recorded in history"
+					);
+				}
+			}
+			Ok(())
+		}
+	}
+
+	/// [`UncheckedMigrateToPerAccount`] run only when the pallet is at storage version 0.
+	pub type MigrateToPerAccount<T, Owner, I = ()> = VersionedMigration<
+		0,
+		1,
+		UncheckedMigrateToPerAccount<T, Owner, I>,
+		Pallet<T, I>,
+		<T as frame_system::Config>::DbWeight,
+	>;
+}
@@ -1,9 +1,32 @@
+//! A mock runtime with two instances of the pallet, for its unit tests and fuzzer.
+
 use crate as pallet_template;
-use frame_support::traits::{ConstU16, ConstU64};
-use sp_core::H256;
+use frame_support::{
+	instances::Instance2,
+	parameter_types,
+	traits::{ConstU16, ConstU32, ConstU64, EnsureOrigin, Hooks},
+	weights::RuntimeDbWeight,
+};
+use frame_system::{
+	offchain::{AppCrypto, CreateSignedTransaction, SendTransactionTypes, SigningTypes},
+	EnsureRootWithSuccess, EnsureSigned,
+};
+use parking_lot::RwLock;
+use sp_core::{
+	offchain::{
+		testing::{OffchainState, PoolState, TestOffchainExt, TestTransactionPoolExt},
+		OffchainDbExt, OffchainWorkerExt, TransactionPoolExt,
+	},
+	H256,
+};
 use sp_runtime::{
-	traits::{BlakeTwo256, IdentityLookup},
-	BuildStorage,
+	testing::{TestSignature, TestXt, UintAuthorityId},
+	traits::{BlakeTwo256, Extrinsic as ExtrinsicT, IdentityLookup},
+	BuildStorage, Perbill,
+};
+use std::{
+	ops::{Deref, DerefMut},
+	sync::Arc,
 };
 
 type Block = frame_system::mocking::MockBlock<Test>;
@@ -13,7 +36,10 @@ frame_support::construct_runtime!(
 	pub enum Test
 	{
 		System: frame_system,
+		Timestamp: pallet_timestamp,
+		Balances: pallet_balances,
 		TemplateModule: pallet_template,
+		SecondTemplate: pallet_template::<Instance2>,
 	}
 );
 
@@ -21,7 +47,7 @@ impl frame_system::Config for Test {
 	type BaseCallFilter = frame_support::traits::Everything;
 	type BlockWeights = ();
 	type BlockLength = ();
-	type DbWeight = ();
+	type DbWeight = MockDbWeight;
 	type RuntimeOrigin = RuntimeOrigin;
 	type RuntimeCall = RuntimeCall;
 	type Nonce = u64;
@@ -34,7 +60,7 @@ impl frame_system::Config for Test {
 	type BlockHashCount = ConstU64<250>;
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
@@ -43,12 +69,257 @@ impl frame_system::Config for Test {
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
 }
 
-impl pallet_template::Config for Test {
+impl pallet_timestamp::Config for Test {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = ConstU64<1>;
+	type WeightInfo = ();
+}
+
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
 	type RuntimeEvent = RuntimeEvent;
+	type DustRemoval = ();
+	type ExistentialDeposit = ConstU64<1>;
+	type AccountStore = System;
 	type WeightInfo = ();
+	type FreezeIdentifier = RuntimeFreezeReason;
+	type MaxFreezes = ConstU32<2>;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type MaxHolds = ConstU32<6>;
+}
+
+/// The origins that [`MockSetOrigin`] can be switched between.
+#[derive(Clone, Copy)]
+pub enum SetOriginKind {
+	AnySigned,
+	Allowlist,
+	Root,
+}
+
+parameter_types! {
+	pub static SetOriginAccepts: SetOriginKind = SetOriginKind::AnySigned;
+	/// Storage accesses are free unless a test sets a cost, as the fuzzer does to count the
+	/// accesses a weight declares.
+	pub static MockDbWeight: RuntimeDbWeight = RuntimeDbWeight { read: 0, write: 0 };
+	pub const RootWriter: u64 = 100;
+	pub const OracleAccount: u64 = 1000;
+	pub const SecondOracleAccount: u64 = 1001;
+	pub const ProposalAccount: u64 = 2000;
+	pub const SecondProposalAccount: u64 = 2001;
+	pub const ApprovalThreshold: Perbill = Perbill::from_percent(50);
+}
+
+/// A `SetOrigin` whose behaviour tests pick by setting [`SetOriginAccepts`].
+pub struct MockSetOrigin;
+impl EnsureOrigin<RuntimeOrigin> for MockSetOrigin {
+	type Success = u64;
+
+	fn try_origin(o: RuntimeOrigin) -> Result<u64, RuntimeOrigin> {
+		match SetOriginAccepts::get() {
+			SetOriginKind::AnySigned => EnsureSigned::<u64>::try_origin(o),
+			SetOriginKind::Allowlist => pallet_template::EnsureWriter::<Test>::try_origin(o),
+			SetOriginKind::Root => EnsureRootWithSuccess::<u64, RootWriter>::try_origin(o),
+		}
+	}
+
+	#[cfg(feature = "runtime-benchmarks")]
+	fn try_successful_origin() -> Result<RuntimeOrigin, ()> {
+		EnsureSigned::<u64>::try_successful_origin()
+	}
+}
+
+/// The extrinsic type the offchain worker submits in tests.
+pub type Extrinsic = TestXt<RuntimeCall, ()>;
+
+impl SigningTypes for Test {
+	type Public = UintAuthorityId;
+	type Signature = TestSignature;
+}
+
+impl<LocalCall> SendTransactionTypes<LocalCall> for Test
+where
+	RuntimeCall: From<LocalCall>,
+{
+	type OverarchingCall = RuntimeCall;
+	type Extrinsic = Extrinsic;
+}
+
+impl<LocalCall> CreateSignedTransaction<LocalCall> for Test
+where
+	RuntimeCall: From<LocalCall>,
+{
+	fn create_transaction<C: AppCrypto<Self::Public, Self::Signature>>(
+		call: RuntimeCall,
+		_public: UintAuthorityId,
+		account: u64,
+		_nonce: u64,
+	) -> Option<(RuntimeCall, <Extrinsic as ExtrinsicT>::SignaturePayload)> {
+		Some((call, (account, ())))
+	}
+}
+
+/// Signs with the keys set by [`UintAuthorityId::set_all_keys`], without a keystore.
+pub struct TestAuthId;
+impl AppCrypto<UintAuthorityId, TestSignature> for TestAuthId {
+	type RuntimeAppPublic = UintAuthorityId;
+	type GenericPublic = UintAuthorityId;
+	type GenericSignature = TestSignature;
+}
+
+impl pallet_template::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = pallet_template::SubstrateWeight<Test>;
+	type MaxHistory = ConstU32<3>;
+	type SetOrigin = MockSetOrigin;
+	type Currency = Balances;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type RuntimeFreezeReason = RuntimeFreezeReason;
+	type ValueDeposit = ConstU64<10>;
+	type MaxScheduledPerBlock = ConstU32<2>;
+	type AuthorityId = TestAuthId;
+	type UnsignedInterval = ConstU64<5>;
+	type UnsignedPriority = ConstU64<100>;
+	type MaxCounterNameLength = ConstU32<8>;
+	type CounterDeposit = ConstU64<10>;
+	type RevealWindow = ConstU64<3>;
+	type MaxExpiringPerBlock = ConstU32<3>;
+	type Time = Timestamp;
+	type MaxFeeders = ConstU32<4>;
+	type RoundLength = ConstU64<10>;
+	type OracleAccount = OracleAccount;
+	type ProposalAccount = ProposalAccount;
+	type MaxBatch = ConstU32<4>;
+	type FreeCallsPerEra = ConstU32<2>;
+	type FreeCallEra = ConstU64<10>;
+	type ProposalDeposit = ConstU64<10>;
+	type VotingPeriod = ConstU64<3>;
+	type ApprovalThreshold = ApprovalThreshold;
+	type MaxProposals = ConstU32<2>;
+	type MaxVoters = ConstU32<3>;
+	type VoteFeeMargin = ConstU64<5>;
+}
+
+/// A second, independently configured instance of the pallet.
+impl pallet_template::Config<Instance2> for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = pallet_template::SubstrateWeight<Test>;
+	type MaxHistory = ConstU32<5>;
+	type SetOrigin = EnsureSigned<u64>;
+	type Currency = Balances;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type RuntimeFreezeReason = RuntimeFreezeReason;
+	type ValueDeposit = ConstU64<20>;
+	type MaxScheduledPerBlock = ConstU32<2>;
+	type AuthorityId = TestAuthId;
+	type UnsignedInterval = ConstU64<5>;
+	type UnsignedPriority = ConstU64<100>;
+	type MaxCounterNameLength = ConstU32<8>;
+	type CounterDeposit = ConstU64<20>;
+	type RevealWindow = ConstU64<3>;
+	type MaxExpiringPerBlock = ConstU32<3>;
+	type Time = Timestamp;
+	type MaxFeeders = ConstU32<4>;
+	type RoundLength = ConstU64<10>;
+	type OracleAccount = SecondOracleAccount;
+	type ProposalAccount = SecondProposalAccount;
+	type MaxBatch = ConstU32<4>;
+	type FreeCallsPerEra = ConstU32<2>;
+	type FreeCallEra = ConstU64<10>;
+	type ProposalDeposit = ConstU64<10>;
+	type VotingPeriod = ConstU64<3>;
+	type ApprovalThreshold = ApprovalThreshold;
+	type MaxProposals = ConstU32<2>;
+	type MaxVoters = ConstU32<3>;
+	type VoteFeeMargin = ConstU64<5>;
+}
+
+/// The balance every account in [`new_test_ext`] starts with.
+pub const INITIAL_BALANCE: u64 = 100;
+
+/// Test externalities that check the pallet's storage invariants after every `execute_with`.
+pub struct TestExt(sp_io::TestExternalities);
+
+impl TestExt {
+	/// Run `test`, then check the invariants of both pallet instances.
+	pub fn execute_with<R>(&mut self, test: impl FnOnce() -> R) -> R {
+		self.0.execute_with(|| {
+			let result = test();
+			TemplateModule::do_try_state().expect("pallet invariants must hold after a test");
+			SecondTemplate::do_try_state().expect("pallet invariants must hold after a test");
+			result
+		})
+	}
+}
+
+impl Deref for TestExt {
+	type Target = sp_io::TestExternalities;
+
+	fn deref(&self) -> &Self::Target {
+		&self.0
+	}
+}
+
+impl DerefMut for TestExt {
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		&mut self.0
+	}
 }
 
 // Build genesis storage according to the mock runtime.
-pub fn new_test_ext() -> sp_io::TestExternalities {
-	frame_system::GenesisConfig::<Test>::default().build_storage().unwrap().into()
+pub fn new_test_ext() -> TestExt {
+	new_test_ext_with(Default::default())
+}
+
+/// [`new_test_ext`] with the given template pallet genesis.
+pub fn new_test_ext_with(template: pallet_template::GenesisConfig<Test>) -> TestExt {
+	let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: [1, 2, 3, 4, 5, RootWriter::get()]
+			.into_iter()
+			.map(|who| (who, INITIAL_BALANCE))
+			.collect(),
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	template.assimilate_storage(&mut t).unwrap();
+	TestExt(t.into())
+}
+
+/// [`new_test_ext`] with the offchain worker and transaction pool extensions registered.
+///
+/// Also returns the offchain state, to answer HTTP requests, and the pool state, to inspect
+/// submitted transactions.
+pub fn new_offchain_test_ext() -> (TestExt, Arc<RwLock<OffchainState>>, Arc<RwLock<PoolState>>) {
+	let (offchain, offchain_state) = TestOffchainExt::new();
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+	let mut t = new_test_ext();
+	t.register_extension(OffchainDbExt::new(offchain.clone()));
+	t.register_extension(OffchainWorkerExt::new(offchain));
+	t.register_extension(TransactionPoolExt::new(pool));
+	(t, offchain_state, pool_state)
+}
+
+/// The time between two blocks in [`run_to_block`], in milliseconds.
+pub const BLOCK_TIME: u64 = 6000;
+
+/// Advance to block `n`, finalizing the current block and initializing each block on the way
+/// with both pallet instances' hooks. Every block is timestamped [`BLOCK_TIME`] after the last.
+pub fn run_to_block(n: u64) {
+	while System::block_number() < n {
+		let now = System::block_number();
+		// The genesis block is never finalized with hooks.
+		if now > 0 {
+			TemplateModule::on_finalize(now);
+			SecondTemplate::on_finalize(now);
+		}
+		let next = now + 1;
+		System::set_block_number(next);
+		Timestamp::set_timestamp(next * BLOCK_TIME);
+		TemplateModule::on_initialize(next);
+		SecondTemplate::on_initialize(next);
+	}
 }
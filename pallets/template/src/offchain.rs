@@ -0,0 +1,213 @@
+//! The template pallet's offchain worker.
+//!
+//! Every block the worker reads a value and submits it back to the chain, either as a signed
+//! [`Call::do_something`] or as an unsigned [`Call::submit_value_unsigned`] carrying a payload
+//! signed with a `tmpl` key from the node's keystore. Insert one with `author_insertKey`; the
+//! worker does nothing on nodes without one. Unsigned submissions are only accepted from accounts
+//! in [`AllowedWriters`].
+//!
+//! Both kinds of submission are sent at most once every [`Config::UnsignedInterval`] blocks. The
+//! chain enforces this for unsigned ones, while the worker keeps track of its signed ones under
+//! [`OCW_LAST_SIGNED_KEY`], and also skips a signed submission of the value it last submitted.
+//!
+//! The worker is configured through the node's persistent offchain storage, which can be written
+//! with the `offchain_localStorageSet` RPC. All values are SCALE encoded. Each key is prefixed
+//! with the pallet's name in `construct_runtime!`, so every instance is configured separately,
+//! e.g. `TemplateModule::ocw::value`.
+//!
+//! - [`OCW_VALUE_KEY`]: a `u32` to submit. When unset, the value is fetched over HTTP.
+//! - [`OCW_URL_KEY`]: the URL to fetch the value from when [`OCW_VALUE_KEY`] is unset. The
+//!   endpoint must answer with the value as a decimal number. Nothing is fetched when unset.
+//! - [`OCW_UNSIGNED_KEY`]: a `bool`, submit unsigned transactions when `true`.
+
+use super::*;
+use codec::{Decode, Encode};
+use frame_support::traits::PalletInfoAccess;
+use frame_system::{
+	offchain::{
+		SendSignedTransaction, SendUnsignedTransaction, SignedPayload, Signer, SigningTypes,
+	},
+	pallet_prelude::BlockNumberFor,
+};
+use scale_info::TypeInfo;
+use sp_core::crypto::KeyTypeId;
+use sp_runtime::{
+	offchain::{
+		http,
+		storage::{MutateStorageError, StorageRetrievalError, StorageValueRef},
+		Duration,
+	},
+	RuntimeDebug,
+};
+use sp_std::vec::Vec;
+
+/// The key type of the keys the offchain worker signs with.
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"tmpl");
+
+/// Offchain storage key of a fixed value to submit instead of fetching one.
+pub const OCW_VALUE_KEY: &[u8] = b"::ocw::value";
+/// Offchain storage key of the URL to fetch the value from.
+pub const OCW_URL_KEY: &[u8] = b"::ocw::url";
+/// Offchain storage key of the flag selecting unsigned submission.
+pub const OCW_UNSIGNED_KEY: &[u8] = b"::ocw::unsigned";
+/// Offchain storage key of the block and value of the worker's last signed submission, written
+/// by the worker itself.
+pub const OCW_LAST_SIGNED_KEY: &[u8] = b"::ocw::last-signed";
+
+/// How long to wait for the HTTP endpoint to answer, in milliseconds.
+const HTTP_TIMEOUT_MS: u64 = 2_000;
+
+/// The application crypto used to sign the offchain worker's transactions.
+pub mod crypto {
+	use super::KEY_TYPE;
+	use sp_runtime::{
+		app_crypto::{app_crypto, sr25519},
+		MultiSignature, MultiSigner,
+	};
+	app_crypto!(sr25519, KEY_TYPE);
+
+	/// Signs with the node's `tmpl` sr25519 keys, for runtimes using [`MultiSignature`].
+	pub struct TemplateAuthId;
+
+	impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for TemplateAuthId {
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
+}
+
+/// The payload of [`Call::submit_value_unsigned`], signed by `public`.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct ValuePayload<Public, BlockNumber> {
+	/// The block the worker that produced the payload ran at.
+	pub block_number: BlockNumber,
+	/// The key that signed the payload. The value is stored for its account.
+	pub public: Public,
+	/// The value to store.
+	pub value: u32,
+}
+
+impl<T: SigningTypes> SignedPayload<T> for ValuePayload<T::Public, BlockNumberFor<T>> {
+	fn public(&self) -> T::Public {
+		self.public.clone()
+	}
+}
+
+impl<T: Config<I>, I: 'static> Pallet<T, I> {
+	/// The offchain storage key of this instance for `key`, one of the `OCW_*_KEY` constants.
+	pub fn offchain_key(key: &[u8]) -> Vec<u8> {
+		[<Self as PalletInfoAccess>::name().as_bytes(), key].concat()
+	}
+
+	/// Read a value and submit it the way the offchain storage configuration asks for.
+	///
+	/// Returns before reading anything if the node has no key to sign with.
+	pub(crate) fn offchain_submit(block_number: BlockNumberFor<T>) -> Result<(), &'static str> {
+		if !Signer::<T, T::AuthorityId>::any_account().can_sign() {
+			return Err("no local account available for signing")
+		}
+		let value = Self::offchain_value()?;
+		let unsigned = StorageValueRef::persistent(&Self::offchain_key(OCW_UNSIGNED_KEY))
+			.get::<bool>()
+			.ok()
+			.flatten();
+		if unsigned.unwrap_or(false) {
+			Self::submit_unsigned(block_number, value)
+		} else {
+			Self::submit_signed(block_number, value)
+		}
+	}
+
+	/// The configured fixed value, or else the value served by the configured URL, if any.
+	fn offchain_value() -> Result<u32, &'static str> {
+		if let Ok(Some(value)) =
+			StorageValueRef::persistent(&Self::offchain_key(OCW_VALUE_KEY)).get::<u32>()
+		{
+			return Ok(value)
+		}
+
+		let url = StorageValueRef::persistent(&Self::offchain_key(OCW_URL_KEY))
+			.get::<Vec<u8>>()
+			.ok()
+			.flatten()
+			.ok_or("no value or URL configured")?;
+		let url = sp_std::str::from_utf8(&url).map_err(|_| "configured URL is not UTF-8")?;
+		Self::fetch_value(url)
+	}
+
+	/// Fetch `url` and parse its body as a decimal `u32`.
+	fn fetch_value(url: &str) -> Result<u32, &'static str> {
+		let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(HTTP_TIMEOUT_MS));
+		let pending = http::Request::get(url)
+			.deadline(deadline)
+			.send()
+			.map_err(|_| "failed to send HTTP request")?;
+		let response = pending
+			.try_wait(deadline)
+			.map_err(|_| "HTTP request timed out")?
+			.map_err(|_| "HTTP request failed")?;
+		if response.code != 200 {
+			return Err("unexpected HTTP status code")
+		}
+
+		let body = response.body().collect::<Vec<u8>>();
+		sp_std::str::from_utf8(&body)
+			.ok()
+			.and_then(|body| body.trim().parse().ok())
+			.ok_or("HTTP response is not a number")
+	}
+
+	/// Submit `value` as a [`Call::do_something`] signed by any local `tmpl` key, unless the
+	/// last signed submission was less than [`Config::UnsignedInterval`] blocks ago or of the same
+	/// value.
+	fn submit_signed(block_number: BlockNumberFor<T>, value: u32) -> Result<(), &'static str> {
+		// Claim the submission with a compare-and-set, so that workers running for several blocks
+		// at once can't all submit.
+		let last = StorageValueRef::persistent(&Self::offchain_key(OCW_LAST_SIGNED_KEY));
+		let claimed =
+			last.mutate(|last: Result<Option<(BlockNumberFor<T>, u32)>, StorageRetrievalError>| {
+				match last {
+					Ok(Some((at, _)))
+						if block_number < at.saturating_add(T::UnsignedInterval::get()) =>
+						Err("too early to submit a signed transaction"),
+					Ok(Some((_, sent))) if sent == value => Err("value already submitted"),
+					_ => Ok((block_number, value)),
+				}
+			});
+		match claimed {
+			Ok(_) => {},
+			Err(MutateStorageError::ValueFunctionFailed(e)) => return Err(e),
+			Err(MutateStorageError::ConcurrentModification(_)) =>
+				return Err("another worker is submitting a signed transaction"),
+		}
+
+		let result = match Signer::<T, T::AuthorityId>::any_account()
+			.send_signed_transaction(|_| Call::<T, I>::do_something { something: value })
+		{
+			Some((_, Ok(()))) => Ok(()),
+			Some((_, Err(()))) => Err("failed to submit signed transaction"),
+			None => Err("no local account available for signing"),
+		};
+		if result.is_err() {
+			// Let the next worker try again.
+			last.clear();
+		}
+		result
+	}
+
+	/// Submit `value` as a [`Call::submit_value_unsigned`] with a payload signed by any local
+	/// `tmpl` key.
+	fn submit_unsigned(block_number: BlockNumberFor<T>, value: u32) -> Result<(), &'static str> {
+		if NextUnsignedAt::<T, I>::get() > block_number {
+			return Err("too early to submit an unsigned transaction")
+		}
+
+		let (_, result) = Signer::<T, T::AuthorityId>::any_account()
+			.send_unsigned_transaction(
+				|account| ValuePayload { block_number, public: account.public.clone(), value },
+				|payload, signature| Call::<T, I>::submit_value_unsigned { payload, signature },
+			)
+			.ok_or("no local account available for signing")?;
+		result.map_err(|()| "failed to submit unsigned transaction")
+	}
+}
@@ -0,0 +1,953 @@
+//! Property tests comparing the default pallet instance against a reference model.
+//!
+//! Each case runs a random sequence of [`Op`]s against both [`Model`] and the mock runtime, and
+//! checks after every step that they returned the same result, emitted the same events and
+//! ended up with the same storage. On a failure, proptest shrinks the sequence to a minimal one.
+
+use crate::{
+	mock::*, Aggregated, AggregatedValue, AllowedWriters, BatchMode, Call, Commitment, Commitments,
+	CounterInfo, Counters, Error, ExpiresAt, Expiring, Feeders, Feeds, FreezeReason, History,
+	HistoryEntry, HoldReason, NextUnsignedAt, Proposal, ProposalCount, Proposals, ProposalsEnding,
+	Round, Scheduled, ScheduledChange, Something, ValuePayload, Vote, VoteLocks, Votes,
+};
+use codec::Encode;
+use frame_support::{
+	traits::{
+		fungible::{InspectFreeze, InspectHold, Mutate},
+		Get, Hooks,
+	},
+	weights::Weight,
+	BoundedVec,
+};
+use proptest::prelude::*;
+use sp_runtime::{
+	testing::{TestSignature, UintAuthorityId},
+	traits::Dispatchable,
+	DispatchError, DispatchResult, PerThing, TokenError,
+};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// The events of the default pallet instance.
+type Event = crate::Event<Test>;
+
+/// An account that can't afford a deposit until it is handed a counter and deletes it.
+const POOR: u64 = 6;
+
+/// The accounts that calls are made by and refer to.
+const ACCOUNTS: [u64; 5] = [1, 2, 3, 4, POOR];
+
+/// The balance of [`POOR`].
+const POOR_BALANCE: u64 = 5;
+
+/// The proposal indices votes refer to.
+const PROPOSALS: u32 = 4;
+
+/// The counter names calls refer to.
+const COUNTER_NAMES: u8 = 3;
+
+/// The most blocks a single [`Op::AdvanceBlocks`] moves forward.
+const MAX_ADVANCE: u64 = 4;
+
+fn deposit() -> u64 {
+	<Test as crate::Config>::ValueDeposit::get()
+}
+
+fn max_batch() -> usize {
+	<Test as crate::Config>::MaxBatch::get() as usize
+}
+
+fn reveal_window() -> u64 {
+	<Test as crate::Config>::RevealWindow::get()
+}
+
+fn held(who: u64) -> u64 {
+	Balances::balance_on_hold(&RuntimeHoldReason::TemplateModule(HoldReason::ValueDeposit), &who)
+}
+
+fn proposal_held(who: u64) -> u64 {
+	Balances::balance_on_hold(&RuntimeHoldReason::TemplateModule(HoldReason::ProposalDeposit), &who)
+}
+
+fn counter_held(who: u64) -> u64 {
+	Balances::balance_on_hold(&RuntimeHoldReason::TemplateModule(HoldReason::CounterDeposit), &who)
+}
+
+fn frozen(who: u64) -> u64 {
+	Balances::balance_frozen(&RuntimeFreezeReason::TemplateModule(FreezeReason::Vote), &who)
+}
+
+/// The balances of [`ACCOUNTS`] for which `balance` is not zero.
+fn non_zero(balance: impl Fn(u64) -> u64) -> BTreeMap<u64, u64> {
+	ACCOUNTS
+		.into_iter()
+		.map(|who| (who, balance(who)))
+		.filter(|(_, balance)| *balance > 0)
+		.collect()
+}
+
+fn counter_name(name: u8) -> Vec<u8> {
+	vec![b'a' + name]
+}
+
+fn salt(salt: u8) -> [u8; 32] {
+	[salt; 32]
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Origin {
+	Root,
+	Signed(u64),
+}
+
+impl From<Origin> for RuntimeOrigin {
+	fn from(origin: Origin) -> Self {
+		match origin {
+			Origin::Root => RuntimeOrigin::root(),
+			Origin::Signed(who) => RuntimeOrigin::signed(who),
+		}
+	}
+}
+
+/// A call to the pallet, with blocks given relative to the current one.
+#[derive(Clone, Debug)]
+enum TemplateCall {
+	DoSomething { value: u32 },
+	CauseError,
+	ClearSomething,
+	AddWriter { who: u64 },
+	RemoveWriter { who: u64 },
+	ScheduleSet { value: u32, in_blocks: u64 },
+	CancelScheduled { in_blocks: u64 },
+	CreateCounter { name: u8, value: u64, min: Option<u64>, max: Option<u64> },
+	Increment { name: u8, by: u64 },
+	Decrement { name: u8, by: u64 },
+	Reset { name: u8 },
+	TransferOwnership { name: u8, new_owner: u64 },
+	DeleteCounter { name: u8 },
+	Commit { value: u32, salt: u8 },
+	Reveal { value: u32, salt: u8 },
+	AddFeeder { who: u64 },
+	RemoveFeeder { who: u64 },
+	Feed { value: u32 },
+	DoSomethingWithTtl { value: u32, ttl: u64 },
+	SetMany { values: Vec<(u64, u32)>, mode: BatchMode },
+	Propose { value: u32 },
+	Vote { index: u32, aye: bool, balance: u64 },
+}
+
+impl TemplateCall {
+	fn into_call(self, now: u64) -> Call<Test> {
+		let name = |name| BoundedVec::truncate_from(counter_name(name));
+		match self {
+			Self::DoSomething { value } => Call::do_something { something: value },
+			Self::CauseError => Call::cause_error {},
+			Self::ClearSomething => Call::clear_something {},
+			Self::AddWriter { who } => Call::add_writer { who },
+			Self::RemoveWriter { who } => Call::remove_writer { who },
+			Self::ScheduleSet { value, in_blocks } =>
+				Call::schedule_set { value, at: now + in_blocks },
+			Self::CancelScheduled { in_blocks } => Call::cancel_scheduled { at: now + in_blocks },
+			Self::CreateCounter { name: n, value, min, max } =>
+				Call::create_counter { name: name(n), value, min, max },
+			Self::Increment { name: n, by } => Call::increment { name: name(n), by },
+			Self::Decrement { name: n, by } => Call::decrement { name: name(n), by },
+			Self::Reset { name: n } => Call::reset { name: name(n) },
+			Self::TransferOwnership { name: n, new_owner } =>
+				Call::transfer_ownership { name: name(n), new_owner },
+			Self::DeleteCounter { name: n } => Call::delete_counter { name: name(n) },
+			Self::Commit { value, salt: s } =>
+				Call::commit { hash: TemplateModule::commitment_hash(value, &salt(s)) },
+			Self::Reveal { value, salt: s } => Call::reveal { value, salt: salt(s) },
+			Self::AddFeeder { who } => Call::add_feeder { who },
+			Self::RemoveFeeder { who } => Call::remove_feeder { who },
+			Self::Feed { value } => Call::feed { value },
+			Self::DoSomethingWithTtl { value, ttl } =>
+				Call::do_something_with_ttl { something: value, ttl },
+			Self::SetMany { values, mode } =>
+				Call::set_many { values: BoundedVec::truncate_from(values), mode },
+			Self::Propose { value } => Call::propose { value },
+			Self::Vote { index, aye, balance } => Call::vote { index, aye, balance },
+		}
+	}
+}
+
+#[derive(Clone, Debug)]
+enum Op {
+	/// Dispatch a call from `origin`.
+	Call { origin: Origin, call: TemplateCall },
+	/// Dispatch [`Call::submit_value_unsigned`] for a payload signed by `key`.
+	Unsigned { key: u64, value: u32 },
+	/// Finalize the current block and initialize the given number of new ones.
+	AdvanceBlocks(u64),
+	/// Run `on_idle` with all the weight it wants.
+	Idle,
+}
+
+/// The pallet's storage, as seen by the tests.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct State {
+	balances: BTreeMap<u64, u64>,
+	values: BTreeMap<u64, u32>,
+	held: BTreeMap<u64, u64>,
+	writers: BTreeSet<u64>,
+	history: Vec<HistoryEntry<u64, u64>>,
+	scheduled: BTreeMap<u64, Vec<ScheduledChange<u64>>>,
+	counters: BTreeMap<Vec<u8>, CounterInfo<u64, u64>>,
+	counter_held: BTreeMap<u64, u64>,
+	commitments: BTreeMap<u64, Commitment<u64>>,
+	feeders: Vec<u64>,
+	feeds: Vec<(u64, u32)>,
+	round: u32,
+	aggregated: Option<AggregatedValue<u64>>,
+	expires_at: BTreeMap<u64, u64>,
+	expiring: BTreeMap<u64, Vec<u64>>,
+	next_unsigned_at: u64,
+	proposal_count: u32,
+	proposals: BTreeMap<u32, Proposal<u64, u64, u64>>,
+	proposals_ending: BTreeMap<u64, Vec<u32>>,
+	proposal_held: BTreeMap<u64, u64>,
+	votes: BTreeMap<(u32, u64), Vote<u64>>,
+	vote_locks: BTreeMap<u64, Vec<(u32, u64)>>,
+	frozen: BTreeMap<u64, u64>,
+}
+
+impl State {
+	/// Read the state of the default pallet instance from storage.
+	fn read() -> Self {
+		Self {
+			balances: non_zero(|who| Balances::total_balance(&who)),
+			values: Something::<Test>::iter().collect(),
+			held: non_zero(held),
+			writers: AllowedWriters::<Test>::iter_keys().collect(),
+			history: History::<Test>::get().into_inner(),
+			scheduled: Scheduled::<Test>::iter()
+				.map(|(at, changes)| (at, changes.into_inner()))
+				.collect(),
+			counters: Counters::<Test>::iter()
+				.map(|(name, counter)| (name.into_inner(), counter))
+				.collect(),
+			counter_held: non_zero(counter_held),
+			commitments: Commitments::<Test>::iter().collect(),
+			feeders: Feeders::<Test>::get().into_inner(),
+			feeds: Feeds::<Test>::get().into_inner(),
+			round: Round::<Test>::get(),
+			aggregated: Aggregated::<Test>::get(),
+			expires_at: ExpiresAt::<Test>::iter().collect(),
+			expiring: Expiring::<Test>::iter()
+				.map(|(at, accounts)| (at, accounts.into_inner()))
+				.collect(),
+			next_unsigned_at: NextUnsignedAt::<Test>::get(),
+			proposal_count: ProposalCount::<Test>::get(),
+			proposals: Proposals::<Test>::iter().collect(),
+			proposals_ending: ProposalsEnding::<Test>::iter()
+				.map(|(end, indices)| (end, indices.into_inner()))
+				.collect(),
+			proposal_held: non_zero(proposal_held),
+			votes: Votes::<Test>::iter().map(|(index, who, vote)| ((index, who), vote)).collect(),
+			vote_locks: VoteLocks::<Test>::iter()
+				.map(|(who, locks)| (who, locks.into_inner()))
+				.collect(),
+			frozen: non_zero(frozen),
+		}
+	}
+}
+
+/// A straightforward reimplementation of the default pallet instance in the mock runtime.
+#[derive(Clone, Debug)]
+struct Model {
+	now: u64,
+	state: State,
+	events: Vec<Event>,
+}
+
+impl Model {
+	fn new(now: u64) -> Self {
+		let balances = ACCOUNTS
+			.into_iter()
+			.map(|who| (who, if who == POOR { POOR_BALANCE } else { INITIAL_BALANCE }))
+			.collect();
+		Self { now, state: State { balances, ..Default::default() }, events: Vec::new() }
+	}
+
+	/// Apply `op`, returning the result of the call it makes, if any, and the events emitted.
+	fn apply(&mut self, op: Op) -> (DispatchResult, Vec<Event>) {
+		let result = match op {
+			Op::Call { origin, call } => self.dispatch(|model| model.call(origin, call)),
+			Op::Unsigned { key, value } => self.dispatch(|model| model.submit_unsigned(key, value)),
+			Op::AdvanceBlocks(blocks) => {
+				for _ in 0..blocks {
+					self.next_block();
+				}
+				Ok(())
+			},
+			Op::Idle => {
+				self.idle();
+				Ok(())
+			},
+		};
+		(result, std::mem::take(&mut self.events))
+	}
+
+	/// Run `f` like a dispatched call, discarding all its changes if it fails.
+	fn dispatch(&mut self, f: impl FnOnce(&mut Self) -> DispatchResult) -> DispatchResult {
+		let mut model = self.clone();
+		f(&mut model)?;
+		*self = model;
+		Ok(())
+	}
+
+	fn call(&mut self, origin: Origin, call: TemplateCall) -> DispatchResult {
+		let now = self.now;
+		match call {
+			TemplateCall::DoSomething { value } => {
+				let who = signed(origin)?;
+				let old = self.set_value(who, value)?;
+				self.events.push(Event::SomethingStored { who, old, new: value });
+			},
+			TemplateCall::CauseError => {
+				let who = signed(origin)?;
+				let old = *self.state.values.get(&who).ok_or(Error::<Test>::NoneValue)?;
+				let new = old.checked_add(1).ok_or(Error::<Test>::StorageOverflow)?;
+				self.state.values.insert(who, new);
+				self.record_history(who, new);
+				self.events.push(Event::SomethingStored { who, old: Some(old), new });
+			},
+			TemplateCall::ClearSomething => {
+				let who = signed(origin)?;
+				let old = self.state.values.remove(&who).ok_or(Error::<Test>::NoneValue)?;
+				self.clear_expiry(who);
+				self.state.held.remove(&who);
+				self.events.push(Event::SomethingCleared { who, old });
+			},
+			TemplateCall::AddWriter { who } => {
+				root(origin)?;
+				ensure(self.state.writers.insert(who), Error::<Test>::AlreadyWriter)?;
+				self.events.push(Event::WriterAdded { who });
+			},
+			TemplateCall::RemoveWriter { who } => {
+				root(origin)?;
+				ensure(self.state.writers.remove(&who), Error::<Test>::NotWriter)?;
+				self.events.push(Event::WriterRemoved { who });
+			},
+			TemplateCall::ScheduleSet { value, in_blocks } => {
+				let who = signed(origin)?;
+				let at = now + in_blocks;
+				ensure(at > now, Error::<Test>::ScheduleInPast)?;
+				let scheduled = self.state.scheduled.entry(at).or_default();
+				ensure(
+					!scheduled.iter().any(|change| change.who == who),
+					Error::<Test>::AlreadyScheduled,
+				)?;
+				ensure(
+					(scheduled.len() as u32) < <Test as crate::Config>::MaxScheduledPerBlock::get(),
+					Error::<Test>::TooManyScheduled,
+				)?;
+				scheduled.push(ScheduledChange { who, value });
+				self.events.push(Event::ValueScheduled { who, value, at });
+			},
+			TemplateCall::CancelScheduled { in_blocks } => {
+				let who = signed(origin)?;
+				let at = now + in_blocks;
+				let scheduled =
+					self.state.scheduled.get_mut(&at).ok_or(Error::<Test>::NotScheduled)?;
+				let index = scheduled
+					.iter()
+					.position(|change| change.who == who)
+					.ok_or(Error::<Test>::NotScheduled)?;
+				scheduled.remove(index);
+				if scheduled.is_empty() {
+					self.state.scheduled.remove(&at);
+				}
+				self.events.push(Event::ScheduleCancelled { who, at });
+			},
+			TemplateCall::CreateCounter { name, value, min, max } => {
+				let owner = signed(origin)?;
+				let name = counter_name(name);
+				ensure(!self.state.counters.contains_key(&name), Error::<Test>::CounterExists)?;
+				let deposit = <Test as crate::Config>::CounterDeposit::get();
+				let counter = CounterInfo { owner, deposit, value, min, max };
+				ensure(counter.allows(value), Error::<Test>::OutOfBounds)?;
+				ensure(self.can_hold(owner, deposit), TokenError::FundsUnavailable)?;
+				*self.state.counter_held.entry(owner).or_default() += deposit;
+				self.state.counters.insert(name.clone(), counter);
+				self.events.push(Event::CounterCreated {
+					name: BoundedVec::truncate_from(name),
+					owner,
+					value,
+				});
+			},
+			TemplateCall::Increment { name, by } =>
+				self.update_counter(origin, name, |counter| {
+					counter.value.checked_add(by).ok_or(Error::<Test>::StorageOverflow)
+				})?,
+			TemplateCall::Decrement { name, by } =>
+				self.update_counter(origin, name, |counter| {
+					counter.value.checked_sub(by).ok_or(Error::<Test>::Underflow)
+				})?,
+			TemplateCall::Reset { name } =>
+				self.update_counter(origin, name, |counter| Ok(counter.reset_value()))?,
+			TemplateCall::TransferOwnership { name, new_owner } => {
+				let who = signed(origin)?;
+				let name = counter_name(name);
+				let counter =
+					self.state.counters.get(&name).ok_or(Error::<Test>::UnknownCounter)?;
+				ensure(counter.owner == who, Error::<Test>::NotCounterOwner)?;
+				let deposit = counter.deposit;
+				// Held funds can't be moved away from a balance frozen for votes.
+				let frozen = self.state.frozen.get(&who).copied().unwrap_or_default();
+				let unfrozen =
+					self.on_hold(who).saturating_sub(frozen.saturating_sub(self.free_balance(who)));
+				ensure(deposit <= unfrozen, TokenError::Frozen)?;
+				self.release_counter_deposit(who, deposit);
+				*self.state.balances.entry(who).or_default() -= deposit;
+				*self.state.balances.entry(new_owner).or_default() += deposit;
+				*self.state.counter_held.entry(new_owner).or_default() += deposit;
+				self.state.counters.get_mut(&name).expect("checked above").owner = new_owner;
+				self.events.push(Event::CounterOwnershipTransferred {
+					name: BoundedVec::truncate_from(name),
+					from: who,
+					to: new_owner,
+				});
+			},
+			TemplateCall::DeleteCounter { name } => {
+				let who = signed(origin)?;
+				let name = counter_name(name);
+				let counter =
+					self.state.counters.get(&name).ok_or(Error::<Test>::UnknownCounter)?;
+				ensure(counter.owner == who, Error::<Test>::NotCounterOwner)?;
+				let deposit = counter.deposit;
+				self.state.counters.remove(&name);
+				self.release_counter_deposit(who, deposit);
+				self.events.push(Event::CounterDeleted {
+					name: BoundedVec::truncate_from(name),
+					owner: who,
+				});
+			},
+			TemplateCall::Commit { value, salt: s } => {
+				let who = signed(origin)?;
+				if let Some(existing) = self.state.commitments.get(&who) {
+					ensure(
+						existing.committed_at + reveal_window() < now,
+						Error::<Test>::AlreadyCommitted,
+					)?;
+				}
+				let hash = TemplateModule::commitment_hash(value, &salt(s));
+				self.state.commitments.insert(who, Commitment { hash, committed_at: now });
+				self.events.push(Event::Committed { who, reveal_by: now + reveal_window() });
+			},
+			TemplateCall::Reveal { value, salt: s } => {
+				let who = signed(origin)?;
+				let commitment =
+					self.state.commitments.remove(&who).ok_or(Error::<Test>::NoCommitment)?;
+				ensure(commitment.committed_at < now, Error::<Test>::RevealTooEarly)?;
+				ensure(
+					now <= commitment.committed_at + reveal_window(),
+					Error::<Test>::RevealTooLate,
+				)?;
+				ensure(
+					commitment.hash == TemplateModule::commitment_hash(value, &salt(s)),
+					Error::<Test>::InvalidReveal,
+				)?;
+				let old = self.set_value(who, value)?;
+				self.events.push(Event::SomethingStored { who, old, new: value });
+			},
+			TemplateCall::AddFeeder { who } => {
+				root(origin)?;
+				let index = self
+					.state
+					.feeders
+					.binary_search(&who)
+					.err()
+					.ok_or(Error::<Test>::AlreadyFeeder)?;
+				ensure(
+					(self.state.feeders.len() as u32) < <Test as crate::Config>::MaxFeeders::get(),
+					Error::<Test>::TooManyFeeders,
+				)?;
+				self.state.feeders.insert(index, who);
+				self.events.push(Event::FeederAdded { who });
+			},
+			TemplateCall::RemoveFeeder { who } => {
+				root(origin)?;
+				let index =
+					self.state.feeders.binary_search(&who).map_err(|_| Error::<Test>::NotFeeder)?;
+				self.state.feeders.remove(index);
+				self.state.feeds.retain(|(feeder, _)| *feeder != who);
+				self.events.push(Event::FeederRemoved { who });
+			},
+			TemplateCall::Feed { value } => {
+				let who = signed(origin)?;
+				ensure(self.state.feeders.contains(&who), Error::<Test>::NotFeeder)?;
+				match self.state.feeds.iter_mut().find(|(feeder, _)| *feeder == who) {
+					Some((_, fed)) => *fed = value,
+					None => self.state.feeds.push((who, value)),
+				}
+				self.events.push(Event::Fed { who, round: self.state.round, value });
+			},
+			TemplateCall::DoSomethingWithTtl { value, ttl } => {
+				let who = signed(origin)?;
+				ensure(ttl > 0, Error::<Test>::ZeroTtl)?;
+				let old = self.set_value(who, value)?;
+				let expires_at = now + ttl;
+				let expiring = self.state.expiring.entry(expires_at).or_default();
+				ensure(
+					(expiring.len() as u32) < <Test as crate::Config>::MaxExpiringPerBlock::get(),
+					Error::<Test>::TooManyExpiring,
+				)?;
+				expiring.push(who);
+				self.state.expires_at.insert(who, expires_at);
+				self.events.push(Event::SomethingStored { who, old, new: value });
+			},
+			TemplateCall::SetMany { values, mode } => {
+				let caller = signed(origin)?;
+				for ((who, value), index) in values.into_iter().zip(0u32..) {
+					let result = if who == caller {
+						self.set_value(who, value)
+					} else {
+						Err(Error::<Test>::NotOwnValue.into())
+					};
+					match (result, mode) {
+						(Ok(old), _) => self.events.push(Event::BatchItemSucceeded {
+							index,
+							who,
+							old,
+							new: value,
+						}),
+						(Err(error), BatchMode::Atomic) => return Err(error),
+						(Err(error), BatchMode::BestEffort) =>
+							self.events.push(Event::BatchItemFailed { index, who, value, error }),
+					}
+				}
+			},
+			TemplateCall::Propose { value } => {
+				let proposer = signed(origin)?;
+				ensure(
+					(self.state.proposals.len() as u32) <
+						<Test as crate::Config>::MaxProposals::get(),
+					Error::<Test>::TooManyProposals,
+				)?;
+				let deposit = <Test as crate::Config>::ProposalDeposit::get();
+				ensure(self.can_hold(proposer, deposit), TokenError::FundsUnavailable)?;
+				*self.state.proposal_held.entry(proposer).or_default() += deposit;
+				let index = self.state.proposal_count;
+				self.state.proposal_count += 1;
+				let end = now + <Test as crate::Config>::VotingPeriod::get();
+				self.state.proposals_ending.entry(end).or_default().push(index);
+				let proposal =
+					Proposal { proposer, value, deposit, end, ayes: 0, nays: 0, voters: 0 };
+				self.state.proposals.insert(index, proposal);
+				self.events.push(Event::Proposed { index, proposer, value, end });
+			},
+			TemplateCall::Vote { index, aye, balance } => {
+				let who = signed(origin)?;
+				let votable = self
+					.free_balance(who)
+					.saturating_sub(<Test as pallet_balances::Config>::ExistentialDeposit::get())
+					.saturating_sub(<Test as crate::Config>::VoteFeeMargin::get());
+				let proposal =
+					self.state.proposals.get_mut(&index).ok_or(Error::<Test>::UnknownProposal)?;
+				ensure(!self.state.votes.contains_key(&(index, who)), Error::<Test>::AlreadyVoted)?;
+				ensure(
+					proposal.voters < <Test as crate::Config>::MaxVoters::get(),
+					Error::<Test>::TooManyVoters,
+				)?;
+				ensure(balance != 0, Error::<Test>::NoVotingBalance)?;
+				ensure(balance <= votable, Error::<Test>::InsufficientVotingBalance)?;
+				if aye {
+					proposal.ayes += balance;
+				} else {
+					proposal.nays += balance;
+				}
+				proposal.voters += 1;
+				let (ayes, nays) = (proposal.ayes, proposal.nays);
+				self.state.vote_locks.entry(who).or_default().push((index, balance));
+				let frozen = self.state.frozen.entry(who).or_default();
+				*frozen = (*frozen).max(balance);
+				self.state.votes.insert((index, who), Vote { aye, balance });
+				self.events.push(Event::Voted { index, who, aye, balance, ayes, nays });
+			},
+		}
+		Ok(())
+	}
+
+	/// The balance of `who` that is not on hold.
+	fn free_balance(&self, who: u64) -> u64 {
+		self.state.balances.get(&who).copied().unwrap_or_default() - self.on_hold(who)
+	}
+
+	/// The balance of `who` on hold for any reason.
+	fn on_hold(&self, who: u64) -> u64 {
+		let held = |held: &BTreeMap<u64, u64>| held.get(&who).copied().unwrap_or_default();
+		held(&self.state.held) + held(&self.state.proposal_held) + held(&self.state.counter_held)
+	}
+
+	/// Whether `amount` can be put on hold for `who` without touching its existential deposit.
+	fn can_hold(&self, who: u64, amount: u64) -> bool {
+		amount < self.free_balance(who)
+	}
+
+	fn release_counter_deposit(&mut self, who: u64, deposit: u64) {
+		let held = self.state.counter_held.get_mut(&who).expect("the deposit is held");
+		*held -= deposit;
+		if *held == 0 {
+			self.state.counter_held.remove(&who);
+		}
+	}
+
+	fn submit_unsigned(&mut self, key: u64, value: u32) -> DispatchResult {
+		ensure(self.state.writers.contains(&key), Error::<Test>::NotWriter)?;
+		let old = self.set_value(key, value)?;
+		self.state.next_unsigned_at = self.now + <Test as crate::Config>::UnsignedInterval::get();
+		self.events.push(Event::SomethingStored { who: key, old, new: value });
+		Ok(())
+	}
+
+	fn update_counter(
+		&mut self,
+		origin: Origin,
+		name: u8,
+		f: impl FnOnce(&CounterInfo<u64, u64>) -> Result<u64, Error<Test>>,
+	) -> DispatchResult {
+		let who = signed(origin)?;
+		let name = counter_name(name);
+		let counter = self.state.counters.get_mut(&name).ok_or(Error::<Test>::UnknownCounter)?;
+		ensure(counter.owner == who, Error::<Test>::NotCounterOwner)?;
+		let new = f(counter)?;
+		ensure(counter.allows(new), Error::<Test>::OutOfBounds)?;
+		let old = std::mem::replace(&mut counter.value, new);
+		self.events.push(Event::CounterUpdated { name: BoundedVec::truncate_from(name), old, new });
+		Ok(())
+	}
+
+	fn set_value(&mut self, who: u64, value: u32) -> Result<Option<u32>, DispatchError> {
+		let old = self.state.values.get(&who).copied();
+		if old.is_none() {
+			ensure(self.can_hold(who, deposit()), TokenError::FundsUnavailable)?;
+			self.state.held.insert(who, deposit());
+		} else {
+			self.clear_expiry(who);
+		}
+		self.state.values.insert(who, value);
+		self.record_history(who, value);
+		Ok(old)
+	}
+
+	fn clear_expiry(&mut self, who: u64) {
+		let Some(at) = self.state.expires_at.remove(&who) else { return };
+		let expiring = self.state.expiring.get_mut(&at).expect("expiring values are indexed");
+		expiring.retain(|account| *account != who);
+		if expiring.is_empty() {
+			self.state.expiring.remove(&at);
+		}
+	}
+
+	fn record_history(&mut self, who: u64, value: u32) {
+		let history = &mut self.state.history;
+		if history.len() as u32 >= <Test as crate::Config>::MaxHistory::get() {
+			history.remove(0);
+		}
+		history.push(HistoryEntry { block: self.now, who, value });
+	}
+
+	/// Finalize the current block and initialize the next one.
+	fn next_block(&mut self) {
+		if self.now % <Test as crate::Config>::RoundLength::get() == 0 {
+			self.close_round();
+		}
+		self.now += 1;
+		for ScheduledChange { who, value } in
+			self.state.scheduled.remove(&self.now).unwrap_or_default()
+		{
+			let event = match self.set_value(who, value) {
+				Ok(old) => Event::ScheduledValueApplied { who, old, new: value },
+				Err(error) => Event::ScheduledValueFailed { who, value, error },
+			};
+			self.events.push(event);
+		}
+		for index in self.state.proposals_ending.remove(&self.now).unwrap_or_default() {
+			self.close_proposal(index);
+		}
+	}
+
+	fn close_proposal(&mut self, index: u32) {
+		let Proposal { proposer, value, deposit, ayes, nays, .. } =
+			self.state.proposals.remove(&index).expect("ending proposals are open");
+		let threshold = <Test as crate::Config>::ApprovalThreshold::get().mul_ceil(ayes + nays);
+		if ayes > 0 && ayes >= threshold {
+			let account = ProposalAccount::get();
+			self.clear_expiry(account);
+			self.state.values.insert(account, value);
+			self.record_history(account, value);
+			self.events.push(Event::ProposalPassed { index, value, ayes, nays });
+		} else {
+			self.events.push(Event::ProposalRejected { index, ayes, nays });
+		}
+
+		let held = self.state.proposal_held.get_mut(&proposer).expect("the deposit is held");
+		*held -= deposit;
+		if *held == 0 {
+			self.state.proposal_held.remove(&proposer);
+		}
+		let voters = self
+			.state
+			.votes
+			.keys()
+			.filter(|(voted, _)| *voted == index)
+			.map(|(_, who)| *who)
+			.collect::<Vec<_>>();
+		for who in voters {
+			self.state.votes.remove(&(index, who));
+			let locks = self.state.vote_locks.get_mut(&who).expect("votes are locked");
+			locks.retain(|(voted, _)| *voted != index);
+			match locks.iter().map(|(_, balance)| *balance).max() {
+				Some(balance) => {
+					self.state.frozen.insert(who, balance);
+				},
+				None => {
+					self.state.vote_locks.remove(&who);
+					self.state.frozen.remove(&who);
+				},
+			}
+		}
+	}
+
+	fn close_round(&mut self) {
+		let round = self.state.round;
+		let mut values = std::mem::take(&mut self.state.feeds)
+			.into_iter()
+			.map(|(_, value)| value)
+			.collect::<Vec<_>>();
+		values.sort();
+		let fed = values.len() as u32;
+		let middle = values.len() / 2;
+		let value = match values.len() {
+			0 => None,
+			len if len % 2 == 1 => Some(values[middle]),
+			// The mean of the middle values, rounded down, without overflowing.
+			_ => Some(((u64::from(values[middle - 1]) + u64::from(values[middle])) / 2) as u32),
+		};
+		if let Some(value) = value {
+			let oracle = OracleAccount::get();
+			self.clear_expiry(oracle);
+			self.state.values.insert(oracle, value);
+			self.record_history(oracle, value);
+			self.state.aggregated =
+				Some(AggregatedValue { value, round, updated_at: self.now * BLOCK_TIME });
+		}
+		self.state.round = round.wrapping_add(1);
+		let feeders = self.state.feeders.len() as u32;
+		self.events.push(Event::RoundFinalized { round, value, fed, feeders });
+	}
+
+	/// Remove all expired values and commitments.
+	fn idle(&mut self) {
+		let due = self.state.expiring.range(..=self.now).map(|(at, _)| *at).collect::<Vec<_>>();
+		for at in due {
+			let expiring = self.state.expiring.remove(&at).unwrap_or_default();
+			// The pallet removes the values of a block from the last one added.
+			for who in expiring.into_iter().rev() {
+				self.state.expires_at.remove(&who);
+				if let Some(value) = self.state.values.remove(&who) {
+					self.state.held.remove(&who);
+					self.events.push(Event::ValueExpired { who, value });
+				}
+			}
+		}
+
+		let now = self.now;
+		let expired = self
+			.state
+			.commitments
+			.iter()
+			.filter(|(_, commitment)| commitment.committed_at + reveal_window() < now)
+			.map(|(who, _)| *who)
+			.collect::<Vec<_>>();
+		for who in expired {
+			self.state.commitments.remove(&who);
+			self.events.push(Event::CommitmentExpired { who });
+		}
+	}
+}
+
+fn signed(origin: Origin) -> Result<u64, DispatchError> {
+	match origin {
+		Origin::Signed(who) => Ok(who),
+		Origin::Root => Err(DispatchError::BadOrigin),
+	}
+}
+
+fn root(origin: Origin) -> DispatchResult {
+	match origin {
+		Origin::Root => Ok(()),
+		Origin::Signed(_) => Err(DispatchError::BadOrigin),
+	}
+}
+
+fn ensure(condition: bool, error: impl Into<DispatchError>) -> DispatchResult {
+	if condition {
+		Ok(())
+	} else {
+		Err(error.into())
+	}
+}
+
+/// Apply `op` to the mock runtime, returning the result of the call it makes, if any, and the
+/// events the default instance emitted.
+fn apply(op: Op) -> (DispatchResult, Vec<Event>) {
+	System::reset_events();
+	let now = System::block_number();
+	let result = match op {
+		Op::Call { origin, call } => RuntimeCall::TemplateModule(call.into_call(now))
+			.dispatch(origin.into())
+			.map(|_| ())
+			.map_err(|e| e.error),
+		Op::Unsigned { key, value } => {
+			let payload = ValuePayload { block_number: now, public: UintAuthorityId(key), value };
+			let signature = TestSignature(key, payload.encode());
+			RuntimeCall::TemplateModule(Call::submit_value_unsigned { payload, signature })
+				.dispatch(RuntimeOrigin::none())
+				.map(|_| ())
+				.map_err(|e| e.error)
+		},
+		Op::AdvanceBlocks(blocks) => {
+			run_to_block(now + blocks);
+			Ok(())
+		},
+		Op::Idle => {
+			TemplateModule::on_idle(now, Weight::MAX);
+			Ok(())
+		},
+	};
+	let events = System::events()
+		.into_iter()
+		.filter_map(|record| match record.event {
+			RuntimeEvent::TemplateModule(event) => Some(event),
+			_ => None,
+		})
+		.collect();
+	(result, events)
+}
+
+/// Sort `events` into a canonical order.
+///
+/// Commitments expire in storage iteration order, which the model does not reproduce, so the
+/// events of a step are compared regardless of their order.
+fn sorted(mut events: Vec<Event>) -> Vec<Event> {
+	events.sort_by_key(|event| event.encode());
+	events
+}
+
+fn account() -> impl Strategy<Value = u64> {
+	prop::sample::select(ACCOUNTS.to_vec())
+}
+
+fn origin() -> impl Strategy<Value = Origin> {
+	prop_oneof![9 => account().prop_map(Origin::Signed), 1 => Just(Origin::Root)]
+}
+
+/// Values around the overflow boundary of [`Call::cause_error`] as well as arbitrary ones.
+fn value() -> impl Strategy<Value = u32> {
+	prop_oneof![0..4u32, Just(u32::MAX - 1), Just(u32::MAX), any::<u32>()]
+}
+
+fn counter_value() -> impl Strategy<Value = u64> {
+	prop_oneof![0..8u64, Just(u64::MAX - 1), Just(u64::MAX)]
+}
+
+fn name() -> impl Strategy<Value = u8> {
+	0..COUNTER_NAMES
+}
+
+fn in_blocks() -> impl Strategy<Value = u64> {
+	0..=MAX_ADVANCE
+}
+
+fn call() -> impl Strategy<Value = TemplateCall> {
+	prop_oneof![
+		value().prop_map(|value| TemplateCall::DoSomething { value }),
+		Just(TemplateCall::CauseError),
+		Just(TemplateCall::ClearSomething),
+		account().prop_map(|who| TemplateCall::AddWriter { who }),
+		account().prop_map(|who| TemplateCall::RemoveWriter { who }),
+		(value(), in_blocks())
+			.prop_map(|(value, in_blocks)| TemplateCall::ScheduleSet { value, in_blocks }),
+		in_blocks().prop_map(|in_blocks| TemplateCall::CancelScheduled { in_blocks }),
+		(
+			name(),
+			counter_value(),
+			prop::option::of(counter_value()),
+			prop::option::of(counter_value())
+		)
+			.prop_map(|(name, value, min, max)| TemplateCall::CreateCounter {
+				name,
+				value,
+				min,
+				max
+			}),
+		(name(), counter_value()).prop_map(|(name, by)| TemplateCall::Increment { name, by }),
+		(name(), counter_value()).prop_map(|(name, by)| TemplateCall::Decrement { name, by }),
+		name().prop_map(|name| TemplateCall::Reset { name }),
+		(name(), account())
+			.prop_map(|(name, new_owner)| TemplateCall::TransferOwnership { name, new_owner }),
+		name().prop_map(|name| TemplateCall::DeleteCounter { name }),
+		(0..2u32, 0..2u8).prop_map(|(value, salt)| TemplateCall::Commit { value, salt }),
+		(0..2u32, 0..2u8).prop_map(|(value, salt)| TemplateCall::Reveal { value, salt }),
+		account().prop_map(|who| TemplateCall::AddFeeder { who }),
+		account().prop_map(|who| TemplateCall::RemoveFeeder { who }),
+		value().prop_map(|value| TemplateCall::Feed { value }),
+		(value(), in_blocks())
+			.prop_map(|(value, ttl)| TemplateCall::DoSomethingWithTtl { value, ttl }),
+		(
+			prop::collection::vec((account(), value()), 0..=max_batch()),
+			prop_oneof![Just(BatchMode::Atomic), Just(BatchMode::BestEffort)]
+		)
+			.prop_map(|(values, mode)| TemplateCall::SetMany { values, mode }),
+		value().prop_map(|value| TemplateCall::Propose { value }),
+		(0..PROPOSALS, any::<bool>(), 0..=INITIAL_BALANCE)
+			.prop_map(|(index, aye, balance)| TemplateCall::Vote { index, aye, balance }),
+	]
+}
+
+fn op() -> impl Strategy<Value = Op> {
+	prop_oneof![
+		12 => (origin(), call()).prop_map(|(origin, call)| Op::Call { origin, call }),
+		1 => (account(), value()).prop_map(|(key, value)| Op::Unsigned { key, value }),
+		3 => (1..=MAX_ADVANCE).prop_map(Op::AdvanceBlocks),
+		1 => Just(Op::Idle),
+	]
+}
+
+proptest! {
+	/// Every sequence of operations has the same results, events and storage changes in the
+	/// pallet as in the model.
+	#[test]
+	fn pallet_matches_model(ops in prop::collection::vec(op(), 1..64)) {
+		SetOriginAccepts::set(SetOriginKind::AnySigned);
+		new_test_ext().execute_with(|| {
+			run_to_block(1);
+			Balances::set_balance(&POOR, POOR_BALANCE);
+			let mut model = Model::new(1);
+
+			for (step, op) in ops.into_iter().enumerate() {
+				let (result, events) = apply(op.clone());
+				let (expected, expected_events) = model.apply(op.clone());
+				prop_assert_eq!(result, expected, "result of step {}: {:?}", step, op);
+				prop_assert_eq!(
+					sorted(events),
+					sorted(expected_events),
+					"events of step {}: {:?}",
+					step,
+					op
+				);
+				prop_assert_eq!(System::block_number(), model.now);
+				prop_assert_eq!(
+					State::read(),
+					model.state.clone(),
+					"storage after step {}: {:?}",
+					step,
+					op
+				);
+			}
+			Ok(())
+		})?;
+	}
+}
@@ -1,5 +1,72 @@
-use crate::{mock::*, Error, Event};
-use frame_support::{assert_noop, assert_ok};
+use crate::{
+	migrations, mock::*, offchain, Aggregated, AggregatedValue, AllowedWriters, BatchMode, BatchOf,
+	Call, Commitment, CommitmentCursor, Commitments, CounterInfo, CounterNameOf, Counters, Error,
+	ExpiresAt, Expiring, ExpiryCursor, Feeders, Feeds, FreeCallPre, FreeCallQuota, FreeCallsUsed,
+	FreezeReason, GenesisConfig, History, HistoryEntry, HoldReason, NextUnsignedAt, ProposalCount,
+	ProposalIndex, Proposals, ProposalsEnding, Round, Scheduled, ScheduledChange, Something,
+	SubstrateWeight, ValuePayload, Vote, VoteLocks, Votes, WeightInfo, FREE_QUOTA_EXHAUSTED,
+};
+use codec::{Decode, Encode};
+use frame_support::{
+	assert_noop, assert_ok,
+	dispatch::GetDispatchInfo,
+	instances::Instance2,
+	parameter_types,
+	traits::{
+		fungible::{Inspect, InspectFreeze, InspectHold, Mutate},
+		tokens::Preservation,
+		ExistenceRequirement, Get, GetStorageVersion, Hooks, OnRuntimeUpgrade, StorageVersion,
+		WithdrawReasons,
+	},
+	weights::Weight,
+};
+use pallet_template_proof::verify_value_proof;
+use sp_core::offchain::testing::PendingRequest;
+use sp_runtime::{
+	generic::Header,
+	offchain::storage::StorageValueRef,
+	testing::{TestSignature, UintAuthorityId},
+	traits::{
+		BlakeTwo256, DispatchInfoOf, Dispatchable, Header as _, SignedExtension, ValidateUnsigned,
+	},
+	transaction_validity::{
+		InvalidTransaction, TransactionSource, TransactionValidity, TransactionValidityError,
+		ValidTransaction,
+	},
+	DispatchError, TokenError,
+};
+
+/// The events of the default pallet instance.
+type Event = crate::Event<Test>;
+
+/// The deposit held by the default pallet instance.
+fn held(who: u64) -> u64 {
+	Balances::balance_on_hold(&RuntimeHoldReason::TemplateModule(HoldReason::ValueDeposit), &who)
+}
+
+/// The deposit held by the second pallet instance.
+fn second_held(who: u64) -> u64 {
+	Balances::balance_on_hold(&RuntimeHoldReason::SecondTemplate(HoldReason::ValueDeposit), &who)
+}
+
+fn counter_name(name: &[u8]) -> CounterNameOf<Test> {
+	name.to_vec().try_into().unwrap()
+}
+
+fn counter_value(name: &CounterNameOf<Test>) -> Option<u64> {
+	Counters::<Test>::get(name).map(|counter| counter.value)
+}
+
+/// The deposit held for counters of the default pallet instance.
+fn counter_held(who: u64) -> u64 {
+	Balances::balance_on_hold(&RuntimeHoldReason::TemplateModule(HoldReason::CounterDeposit), &who)
+}
+
+parameter_types! {
+	pub const Owner: Option<u64> = Some(5);
+	pub const NoOwner: Option<u64> = None;
+	pub const PoorOwner: Option<u64> = Some(6);
+}
 
 #[test]
 fn it_works_for_default_value() {
@@ -9,9 +76,9 @@ fn it_works_for_default_value() {
 		// Dispatch a signed extrinsic.
 		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 42));
 		// Read pallet storage and assert an expected result.
-		assert_eq!(TemplateModule::something(), Some(42));
+		assert_eq!(TemplateModule::something(1), Some(42));
 		// Assert that the correct event was deposited
-		System::assert_last_event(Event::SomethingStored { something: 42, who: 1 }.into());
+		System::assert_last_event(Event::SomethingStored { who: 1, old: None, new: 42 }.into());
 	});
 }
 
@@ -25,3 +92,1907 @@ fn correct_error_for_none_value() {
 		);
 	});
 }
+
+#[test]
+fn values_are_kept_per_account() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 42));
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(2), 7));
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 43));
+		System::assert_last_event(Event::SomethingStored { who: 1, old: Some(42), new: 43 }.into());
+
+		assert_ok!(TemplateModule::cause_error(RuntimeOrigin::signed(2)));
+		System::assert_last_event(Event::SomethingStored { who: 2, old: Some(7), new: 8 }.into());
+
+		assert_eq!(TemplateModule::something(1), Some(43));
+		assert_eq!(TemplateModule::something(2), Some(8));
+		// An account that never wrote cannot increment someone else's value.
+		assert_noop!(
+			TemplateModule::cause_error(RuntimeOrigin::signed(3)),
+			Error::<Test>::NoneValue
+		);
+	});
+}
+
+#[test]
+fn clear_something_removes_only_the_callers_value() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			TemplateModule::clear_something(RuntimeOrigin::signed(1)),
+			Error::<Test>::NoneValue
+		);
+
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 42));
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(2), 7));
+		assert_ok!(TemplateModule::clear_something(RuntimeOrigin::signed(1)));
+		System::assert_last_event(Event::SomethingCleared { who: 1, old: 42 }.into());
+
+		assert_eq!(TemplateModule::something(1), None);
+		assert_eq!(TemplateModule::something(2), Some(7));
+	});
+}
+
+#[test]
+fn first_write_holds_a_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 42));
+		assert_eq!(held(1), 10);
+		assert_eq!(Balances::balance(&1), INITIAL_BALANCE - 10);
+
+		// Overwriting or incrementing an existing value does not take another deposit.
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 43));
+		assert_ok!(TemplateModule::cause_error(RuntimeOrigin::signed(1)));
+		assert_eq!(held(1), 10);
+	});
+}
+
+#[test]
+fn clearing_releases_the_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 42));
+		assert_ok!(TemplateModule::clear_something(RuntimeOrigin::signed(1)));
+		assert_eq!(held(1), 0);
+		assert_eq!(Balances::balance(&1), INITIAL_BALANCE);
+	});
+}
+
+#[test]
+fn cannot_write_without_funds_for_the_deposit() {
+	new_test_ext().execute_with(|| {
+		// Account 6 is not in the mock genesis and can't afford the deposit.
+		Balances::set_balance(&6, 5);
+		assert_noop!(
+			TemplateModule::do_something(RuntimeOrigin::signed(6), 42),
+			TokenError::FundsUnavailable
+		);
+		assert_eq!(TemplateModule::something(6), None);
+	});
+}
+
+#[test]
+fn any_signed_origin_can_write() {
+	new_test_ext().execute_with(|| {
+		SetOriginAccepts::set(SetOriginKind::AnySigned);
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 42));
+		assert_noop!(
+			TemplateModule::do_something(RuntimeOrigin::root(), 42),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			TemplateModule::do_something(RuntimeOrigin::none(), 42),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn only_allowed_writers_can_write() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		SetOriginAccepts::set(SetOriginKind::Allowlist);
+		assert_noop!(
+			TemplateModule::do_something(RuntimeOrigin::signed(1), 42),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(TemplateModule::add_writer(RuntimeOrigin::root(), 1));
+		System::assert_last_event(Event::WriterAdded { who: 1 }.into());
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 42));
+		assert_ok!(TemplateModule::cause_error(RuntimeOrigin::signed(1)));
+		assert_noop!(
+			TemplateModule::do_something(RuntimeOrigin::signed(2), 42),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(TemplateModule::remove_writer(RuntimeOrigin::root(), 1));
+		System::assert_last_event(Event::WriterRemoved { who: 1 }.into());
+		assert_noop!(
+			TemplateModule::cause_error(RuntimeOrigin::signed(1)),
+			DispatchError::BadOrigin
+		);
+		// A removed writer can still clear what it wrote.
+		assert_ok!(TemplateModule::clear_something(RuntimeOrigin::signed(1)));
+	});
+}
+
+#[test]
+fn only_root_can_write() {
+	new_test_ext().execute_with(|| {
+		SetOriginAccepts::set(SetOriginKind::Root);
+		assert_noop!(
+			TemplateModule::do_something(RuntimeOrigin::signed(1), 42),
+			DispatchError::BadOrigin
+		);
+
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::root(), 42));
+		assert_eq!(TemplateModule::something(RootWriter::get()), Some(42));
+	});
+}
+
+#[test]
+fn allowed_writers_are_managed_by_root() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TemplateModule::add_writer(RuntimeOrigin::signed(1), 1),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(TemplateModule::add_writer(RuntimeOrigin::root(), 1));
+		assert!(AllowedWriters::<Test>::contains_key(1));
+		assert_noop!(
+			TemplateModule::add_writer(RuntimeOrigin::root(), 1),
+			Error::<Test>::AlreadyWriter
+		);
+
+		assert_noop!(
+			TemplateModule::remove_writer(RuntimeOrigin::signed(1), 1),
+			DispatchError::BadOrigin
+		);
+		assert_ok!(TemplateModule::remove_writer(RuntimeOrigin::root(), 1));
+		assert_noop!(
+			TemplateModule::remove_writer(RuntimeOrigin::root(), 1),
+			Error::<Test>::NotWriter
+		);
+	});
+}
+
+#[test]
+fn scheduled_values_are_applied_at_their_block() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 1));
+		assert_ok!(TemplateModule::schedule_set(RuntimeOrigin::signed(1), 42, 3));
+		System::assert_last_event(Event::ValueScheduled { who: 1, value: 42, at: 3 }.into());
+		assert_ok!(TemplateModule::schedule_set(RuntimeOrigin::signed(2), 7, 3));
+		assert_eq!(
+			Scheduled::<Test>::get(3).into_inner(),
+			vec![ScheduledChange { who: 1, value: 42 }, ScheduledChange { who: 2, value: 7 }]
+		);
+
+		run_to_block(2);
+		assert_eq!(TemplateModule::something(1), Some(1));
+
+		run_to_block(3);
+		assert_eq!(TemplateModule::something(1), Some(42));
+		assert_eq!(TemplateModule::something(2), Some(7));
+		System::assert_has_event(
+			Event::ScheduledValueApplied { who: 1, old: Some(1), new: 42 }.into(),
+		);
+		System::assert_has_event(Event::ScheduledValueApplied { who: 2, old: None, new: 7 }.into());
+		// Account 2 had no value, so applying the change took its deposit.
+		assert_eq!(held(2), 10);
+		assert!(!Scheduled::<Test>::contains_key(3));
+	});
+}
+
+#[test]
+fn scheduled_value_that_cannot_pay_its_deposit_fails() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		Balances::set_balance(&6, 5);
+		assert_ok!(TemplateModule::schedule_set(RuntimeOrigin::signed(6), 42, 2));
+
+		run_to_block(2);
+		assert_eq!(TemplateModule::something(6), None);
+		System::assert_last_event(
+			Event::ScheduledValueFailed {
+				who: 6,
+				value: 42,
+				error: TokenError::FundsUnavailable.into(),
+			}
+			.into(),
+		);
+	});
+}
+
+#[test]
+fn schedule_set_is_checked() {
+	new_test_ext().execute_with(|| {
+		run_to_block(5);
+		assert_noop!(
+			TemplateModule::schedule_set(RuntimeOrigin::signed(1), 42, 5),
+			Error::<Test>::ScheduleInPast
+		);
+
+		assert_ok!(TemplateModule::schedule_set(RuntimeOrigin::signed(1), 42, 6));
+		assert_noop!(
+			TemplateModule::schedule_set(RuntimeOrigin::signed(1), 43, 6),
+			Error::<Test>::AlreadyScheduled
+		);
+
+		// `MaxScheduledPerBlock` is 2 in the mock.
+		assert_ok!(TemplateModule::schedule_set(RuntimeOrigin::signed(2), 42, 6));
+		assert_noop!(
+			TemplateModule::schedule_set(RuntimeOrigin::signed(3), 42, 6),
+			Error::<Test>::TooManyScheduled
+		);
+	});
+}
+
+#[test]
+fn cancel_scheduled_removes_only_the_callers_change() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_noop!(
+			TemplateModule::cancel_scheduled(RuntimeOrigin::signed(1), 3),
+			Error::<Test>::NotScheduled
+		);
+
+		assert_ok!(TemplateModule::schedule_set(RuntimeOrigin::signed(1), 42, 3));
+		assert_ok!(TemplateModule::schedule_set(RuntimeOrigin::signed(2), 7, 3));
+		assert_ok!(TemplateModule::cancel_scheduled(RuntimeOrigin::signed(1), 3));
+		System::assert_last_event(Event::ScheduleCancelled { who: 1, at: 3 }.into());
+		assert_noop!(
+			TemplateModule::cancel_scheduled(RuntimeOrigin::signed(1), 3),
+			Error::<Test>::NotScheduled
+		);
+
+		assert_ok!(TemplateModule::cancel_scheduled(RuntimeOrigin::signed(2), 3));
+		assert!(!Scheduled::<Test>::contains_key(3));
+
+		run_to_block(3);
+		assert_eq!(TemplateModule::something(1), None);
+		assert_eq!(TemplateModule::something(2), None);
+	});
+}
+
+#[test]
+fn values_with_ttl_expire_in_on_idle() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TemplateModule::do_something_with_ttl(RuntimeOrigin::signed(1), 42, 2));
+		System::assert_last_event(Event::SomethingStored { who: 1, old: None, new: 42 }.into());
+		assert_eq!(ExpiresAt::<Test>::get(1), Some(3));
+		assert_eq!(Expiring::<Test>::get(3).into_inner(), vec![1]);
+		assert_eq!(held(1), 10);
+
+		run_to_block(2);
+		TemplateModule::on_idle(2, Weight::MAX);
+		assert_eq!(TemplateModule::something(1), Some(42));
+
+		run_to_block(3);
+		TemplateModule::on_idle(3, Weight::MAX);
+		System::assert_last_event(Event::ValueExpired { who: 1, value: 42 }.into());
+		assert_eq!(TemplateModule::something(1), None);
+		assert_eq!(ExpiresAt::<Test>::get(1), None);
+		assert!(!Expiring::<Test>::contains_key(3));
+		assert_eq!(ExpiryCursor::<Test>::get(), Some(4));
+		assert_eq!(held(1), 0);
+	});
+}
+
+#[test]
+fn do_something_with_ttl_is_checked() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			TemplateModule::do_something_with_ttl(RuntimeOrigin::signed(1), 42, 0),
+			Error::<Test>::ZeroTtl
+		);
+		for who in 1..=3 {
+			assert_ok!(TemplateModule::do_something_with_ttl(RuntimeOrigin::signed(who), 42, 2));
+		}
+		assert_noop!(
+			TemplateModule::do_something_with_ttl(RuntimeOrigin::signed(4), 42, 2),
+			Error::<Test>::TooManyExpiring
+		);
+		assert_ok!(TemplateModule::do_something_with_ttl(RuntimeOrigin::signed(4), 42, 3));
+	});
+}
+
+#[test]
+fn rewriting_a_value_replaces_its_ttl() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TemplateModule::do_something_with_ttl(RuntimeOrigin::signed(1), 1, 5));
+		assert_ok!(TemplateModule::do_something_with_ttl(RuntimeOrigin::signed(1), 2, 1));
+		assert!(!Expiring::<Test>::contains_key(6));
+		assert_eq!(ExpiresAt::<Test>::get(1), Some(2));
+
+		// Writing without a time-to-live keeps the value.
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 3));
+		assert_eq!(ExpiresAt::<Test>::get(1), None);
+		assert!(!Expiring::<Test>::contains_key(2));
+
+		// So does clearing it and writing it again.
+		assert_ok!(TemplateModule::do_something_with_ttl(RuntimeOrigin::signed(2), 4, 1));
+		assert_ok!(TemplateModule::clear_something(RuntimeOrigin::signed(2)));
+		assert_eq!(ExpiresAt::<Test>::get(2), None);
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(2), 5));
+
+		run_to_block(6);
+		TemplateModule::on_idle(6, Weight::MAX);
+		assert_eq!(TemplateModule::something(1), Some(3));
+		assert_eq!(TemplateModule::something(2), Some(5));
+	});
+}
+
+#[test]
+fn expiry_resumes_where_it_ran_out_of_weight() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		for who in 1..=3 {
+			assert_ok!(TemplateModule::do_something_with_ttl(RuntimeOrigin::signed(who), 7, 1));
+		}
+		assert_ok!(TemplateModule::do_something_with_ttl(RuntimeOrigin::signed(4), 7, 2));
+		run_to_block(3);
+
+		// Each call only has the weight to remove a single value.
+		let one = <Test as frame_system::Config>::DbWeight::get().reads_writes(2, 2) +
+			<SubstrateWeight<Test> as WeightInfo>::expire_value();
+		for remaining in (1..=3).rev() {
+			assert_eq!(TemplateModule::on_idle(3, one), one);
+			assert_eq!(Something::<Test>::iter().count(), remaining);
+		}
+		assert_eq!(ExpiryCursor::<Test>::get(), Some(3));
+		TemplateModule::on_idle(3, one);
+		assert_eq!(Something::<Test>::iter().count(), 0);
+		assert_eq!(ExpiryCursor::<Test>::get(), Some(4));
+		for who in 1..=4 {
+			assert_eq!(held(who), 0);
+		}
+	});
+}
+
+#[test]
+fn history_keeps_the_most_recent_writes() {
+	new_test_ext().execute_with(|| {
+		for (block, value) in (1..=4).zip(10..) {
+			System::set_block_number(block);
+			assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), value));
+		}
+		System::set_block_number(5);
+		assert_ok!(TemplateModule::cause_error(RuntimeOrigin::signed(1)));
+
+		// `MaxHistory` is 3 in the mock, so the writes in blocks 1 and 2 have been dropped.
+		let entry = |block, value| HistoryEntry { block, who: 1, value };
+		assert_eq!(TemplateModule::latest(), Some(entry(5, 14)));
+		assert_eq!(TemplateModule::history(10), vec![entry(5, 14), entry(4, 13), entry(3, 12)]);
+		assert_eq!(TemplateModule::history(1), vec![entry(5, 14)]);
+	});
+}
+
+#[test]
+fn value_at_returns_the_last_write_up_to_a_block() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(TemplateModule::latest(), None);
+
+		System::set_block_number(2);
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 42));
+		System::set_block_number(5);
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(2), 7));
+
+		assert_eq!(TemplateModule::value_at(1), None);
+		assert_eq!(TemplateModule::value_at(4), Some(HistoryEntry { block: 2, who: 1, value: 42 }));
+		assert_eq!(TemplateModule::value_at(9), Some(HistoryEntry { block: 5, who: 2, value: 7 }));
+	});
+}
+
+#[test]
+fn value_proofs_verify_against_the_state_root() {
+	let mut ext = new_test_ext();
+	ext.execute_with(|| {
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 42));
+	});
+	ext.commit_all().unwrap();
+	let header = |root| {
+		Header::<u64, BlakeTwo256>::new(
+			1,
+			Default::default(),
+			root,
+			Default::default(),
+			Default::default(),
+		)
+	};
+
+	// Reading the values records the trie nodes that prove them.
+	let (keys, proof) = ext.execute_and_prove(|| {
+		[1, 2].map(|who| {
+			TemplateModule::something(who);
+			TemplateModule::value_key(&who)
+		})
+	});
+	let [set, unset] = keys;
+	assert_eq!(set, pallet_template_proof::value_key("TemplateModule", &1u64));
+
+	let valid = header(*ext.backend.root());
+	assert_eq!(verify_value_proof(&valid, &set, proof.clone().into_iter_nodes()), Ok(Some(42)));
+	assert_eq!(verify_value_proof(&valid, &unset, proof.clone().into_iter_nodes()), Ok(None));
+	assert_eq!(
+		verify_value_proof(&header(Default::default()), &set, proof.into_iter_nodes()),
+		Err(pallet_template_proof::Error::InvalidProof)
+	);
+}
+
+#[test]
+fn genesis_values_and_writers_are_stored() {
+	new_test_ext_with(GenesisConfig {
+		values: vec![(1, 42), (2, 7)],
+		writers: vec![3],
+		..Default::default()
+	})
+	.execute_with(|| {
+		assert_eq!(TemplateModule::something(1), Some(42));
+		assert_eq!(TemplateModule::something(2), Some(7));
+		assert_eq!((held(1), held(2)), (10, 10));
+		assert!(AllowedWriters::<Test>::contains_key(3));
+		assert_eq!(
+			History::<Test>::get().into_inner(),
+			vec![
+				HistoryEntry { block: 0, who: 1, value: 42 },
+				HistoryEntry { block: 0, who: 2, value: 7 },
+			]
+		);
+	});
+}
+
+#[test]
+#[should_panic(expected = "more than one genesis value")]
+fn genesis_rejects_duplicate_values() {
+	new_test_ext_with(GenesisConfig {
+		values: vec![(1, 42), (1, 7)],
+		writers: vec![],
+		..Default::default()
+	});
+}
+
+#[test]
+#[should_panic(expected = "more than once")]
+fn genesis_rejects_duplicate_writers() {
+	new_test_ext_with(GenesisConfig { values: vec![], writers: vec![3, 3], ..Default::default() });
+}
+
+#[test]
+#[should_panic(expected = "must be able to pay the deposit")]
+fn genesis_rejects_values_that_cannot_pay_the_deposit() {
+	// Account 6 has no balance in the mock genesis.
+	new_test_ext_with(GenesisConfig {
+		values: vec![(6, 42)],
+		writers: vec![],
+		..Default::default()
+	});
+}
+
+#[test]
+fn try_state_detects_broken_invariants() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 42));
+		assert_ok!(TemplateModule::schedule_set(RuntimeOrigin::signed(2), 7, 3));
+		assert_ok!(TemplateModule::do_try_state());
+
+		// A deposit left behind when a value disappears.
+		Something::<Test>::remove(1);
+		assert_eq!(
+			TemplateModule::do_try_state(),
+			Err("account without a value has a deposit held".into())
+		);
+		Something::<Test>::insert(1, 42);
+
+		// A value stored without a deposit.
+		Something::<Test>::insert(3, 7);
+		assert_eq!(
+			TemplateModule::do_try_state(),
+			Err("account with a value has no deposit held".into())
+		);
+		Something::<Test>::remove(3);
+		// Except for the values the pallet writes itself.
+		Something::<Test>::insert(OracleAccount::get(), 7);
+		Something::<Test>::insert(ProposalAccount::get(), 7);
+		assert_ok!(TemplateModule::do_try_state());
+
+		// A scheduled change that was never applied.
+		System::set_block_number(3);
+		assert_eq!(TemplateModule::do_try_state(), Err("change scheduled for a past block".into()));
+		Scheduled::<Test>::remove(3);
+	});
+}
+
+#[test]
+fn instances_are_isolated() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(1), 42));
+		assert_ok!(SecondTemplate::do_something(RuntimeOrigin::signed(1), 7));
+		System::assert_last_event(RuntimeEvent::SecondTemplate(crate::Event::SomethingStored {
+			who: 1,
+			old: None,
+			new: 7,
+		}));
+		assert_eq!(TemplateModule::something(1), Some(42));
+		assert_eq!(SecondTemplate::something(1), Some(7));
+		// Each instance holds its own deposit, sized by its own config.
+		assert_eq!((held(1), second_held(1)), (10, 20));
+
+		assert_ok!(SecondTemplate::clear_something(RuntimeOrigin::signed(1)));
+		assert_eq!(TemplateModule::something(1), Some(42));
+		assert_eq!((held(1), second_held(1)), (10, 0));
+		assert_eq!(TemplateModule::history(10), vec![HistoryEntry { block: 1, who: 1, value: 42 }]);
+		assert_eq!(SecondTemplate::history(10), vec![HistoryEntry { block: 1, who: 1, value: 7 }]);
+		assert_noop!(
+			SecondTemplate::clear_something(RuntimeOrigin::signed(1)),
+			Error::<Test, Instance2>::NoneValue
+		);
+
+		assert_ok!(SecondTemplate::add_writer(RuntimeOrigin::root(), 2));
+		assert!(AllowedWriters::<Test, Instance2>::contains_key(2));
+		assert!(!AllowedWriters::<Test>::contains_key(2));
+
+		// Changes are only applied by the instance they were scheduled in.
+		assert_ok!(SecondTemplate::schedule_set(RuntimeOrigin::signed(2), 8, 2));
+		run_to_block(2);
+		assert_eq!(TemplateModule::something(2), None);
+		assert_eq!(SecondTemplate::something(2), Some(8));
+	});
+}
+
+#[test]
+fn counters_are_created_within_their_bounds() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let seq = counter_name(b"seq");
+		assert_noop!(
+			TemplateModule::create_counter(RuntimeOrigin::signed(1), seq.clone(), 5, Some(6), None),
+			Error::<Test>::OutOfBounds
+		);
+		// No value fits bounds with the minimum above the maximum.
+		assert_noop!(
+			TemplateModule::create_counter(
+				RuntimeOrigin::signed(1),
+				seq.clone(),
+				5,
+				Some(6),
+				Some(4)
+			),
+			Error::<Test>::OutOfBounds
+		);
+
+		assert_ok!(TemplateModule::create_counter(
+			RuntimeOrigin::signed(1),
+			seq.clone(),
+			5,
+			Some(1),
+			Some(10)
+		));
+		assert_eq!(
+			Counters::<Test>::get(&seq),
+			Some(CounterInfo { owner: 1, deposit: 10, value: 5, min: Some(1), max: Some(10) })
+		);
+		assert_eq!(counter_held(1), 10);
+		System::assert_last_event(
+			Event::CounterCreated { name: seq.clone(), owner: 1, value: 5 }.into(),
+		);
+		assert_noop!(
+			TemplateModule::create_counter(RuntimeOrigin::signed(2), seq, 0, None, None),
+			Error::<Test>::CounterExists
+		);
+
+		// Creating counters is limited to `SetOrigin`.
+		SetOriginAccepts::set(SetOriginKind::Allowlist);
+		assert_noop!(
+			TemplateModule::create_counter(
+				RuntimeOrigin::signed(2),
+				counter_name(b"other"),
+				0,
+				None,
+				None
+			),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn counters_stay_within_their_bounds() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let seq = counter_name(b"seq");
+		assert_ok!(TemplateModule::create_counter(
+			RuntimeOrigin::signed(1),
+			seq.clone(),
+			5,
+			Some(2),
+			Some(10)
+		));
+
+		assert_ok!(TemplateModule::increment(RuntimeOrigin::signed(1), seq.clone(), 5));
+		System::assert_last_event(
+			Event::CounterUpdated { name: seq.clone(), old: 5, new: 10 }.into(),
+		);
+		assert_noop!(
+			TemplateModule::increment(RuntimeOrigin::signed(1), seq.clone(), 1),
+			Error::<Test>::OutOfBounds
+		);
+
+		assert_ok!(TemplateModule::decrement(RuntimeOrigin::signed(1), seq.clone(), 7));
+		assert_eq!(counter_value(&seq), Some(3));
+		assert_noop!(
+			TemplateModule::decrement(RuntimeOrigin::signed(1), seq.clone(), 2),
+			Error::<Test>::OutOfBounds
+		);
+
+		// Resetting goes back to the minimum.
+		assert_ok!(TemplateModule::increment(RuntimeOrigin::signed(1), seq.clone(), 4));
+		assert_ok!(TemplateModule::reset(RuntimeOrigin::signed(1), seq.clone()));
+		System::assert_last_event(
+			Event::CounterUpdated { name: seq.clone(), old: 7, new: 2 }.into(),
+		);
+	});
+}
+
+#[test]
+fn unbounded_counters_use_checked_arithmetic() {
+	new_test_ext().execute_with(|| {
+		let seq = counter_name(b"seq");
+		assert_ok!(TemplateModule::create_counter(
+			RuntimeOrigin::signed(1),
+			seq.clone(),
+			u64::MAX - 1,
+			None,
+			None
+		));
+
+		assert_ok!(TemplateModule::increment(RuntimeOrigin::signed(1), seq.clone(), 1));
+		assert_noop!(
+			TemplateModule::increment(RuntimeOrigin::signed(1), seq.clone(), 1),
+			Error::<Test>::StorageOverflow
+		);
+
+		assert_ok!(TemplateModule::reset(RuntimeOrigin::signed(1), seq.clone()));
+		assert_eq!(counter_value(&seq), Some(0));
+		assert_noop!(
+			TemplateModule::decrement(RuntimeOrigin::signed(1), seq.clone(), 1),
+			Error::<Test>::Underflow
+		);
+	});
+}
+
+#[test]
+fn only_the_owner_can_change_a_counter() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let seq = counter_name(b"seq");
+		assert_ok!(TemplateModule::create_counter(
+			RuntimeOrigin::signed(1),
+			seq.clone(),
+			0,
+			None,
+			None
+		));
+
+		assert_noop!(
+			TemplateModule::increment(RuntimeOrigin::signed(2), seq.clone(), 1),
+			Error::<Test>::NotCounterOwner
+		);
+		assert_noop!(
+			TemplateModule::reset(RuntimeOrigin::signed(1), counter_name(b"unknown")),
+			Error::<Test>::UnknownCounter
+		);
+		assert_noop!(
+			TemplateModule::transfer_ownership(RuntimeOrigin::signed(2), seq.clone(), 2),
+			Error::<Test>::NotCounterOwner
+		);
+
+		assert_ok!(TemplateModule::transfer_ownership(RuntimeOrigin::signed(1), seq.clone(), 2));
+		System::assert_last_event(
+			Event::CounterOwnershipTransferred { name: seq.clone(), from: 1, to: 2 }.into(),
+		);
+		assert_noop!(
+			TemplateModule::decrement(RuntimeOrigin::signed(1), seq.clone(), 0),
+			Error::<Test>::NotCounterOwner
+		);
+		assert_ok!(TemplateModule::increment(RuntimeOrigin::signed(2), seq.clone(), 1));
+		assert_eq!(counter_value(&seq), Some(1));
+	});
+}
+
+#[test]
+fn counters_hold_a_deposit_until_deleted() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let seq = counter_name(b"seq");
+		assert_noop!(
+			TemplateModule::create_counter(RuntimeOrigin::signed(6), seq.clone(), 0, None, None),
+			TokenError::FundsUnavailable
+		);
+		assert_ok!(TemplateModule::create_counter(
+			RuntimeOrigin::signed(1),
+			seq.clone(),
+			0,
+			None,
+			None
+		));
+		assert_ok!(TemplateModule::create_counter(
+			RuntimeOrigin::signed(1),
+			counter_name(b"other"),
+			0,
+			None,
+			None
+		));
+		assert_eq!(counter_held(1), 20);
+
+		// The deposit moves with the counter.
+		assert_ok!(TemplateModule::transfer_ownership(RuntimeOrigin::signed(1), seq.clone(), 2));
+		assert_eq!((counter_held(1), counter_held(2)), (10, 10));
+		assert_eq!(Balances::total_balance(&1), 90);
+
+		assert_noop!(
+			TemplateModule::delete_counter(RuntimeOrigin::signed(1), seq.clone()),
+			Error::<Test>::NotCounterOwner
+		);
+		assert_noop!(
+			TemplateModule::delete_counter(RuntimeOrigin::signed(2), counter_name(b"unknown")),
+			Error::<Test>::UnknownCounter
+		);
+		assert_ok!(TemplateModule::delete_counter(RuntimeOrigin::signed(2), seq.clone()));
+		System::assert_last_event(Event::CounterDeleted { name: seq.clone(), owner: 2 }.into());
+		assert_eq!(Counters::<Test>::get(&seq), None);
+		assert_eq!(counter_held(2), 0);
+		assert_eq!(Balances::free_balance(2), 110);
+
+		// The name can be taken again.
+		assert_ok!(TemplateModule::create_counter(RuntimeOrigin::signed(3), seq, 0, None, None));
+		assert_eq!(counter_held(3), 10);
+	});
+}
+
+#[test]
+fn counter_deposit_cannot_be_moved_out_of_a_vote() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let seq = counter_name(b"seq");
+		assert_ok!(TemplateModule::propose(RuntimeOrigin::signed(2), 7));
+		assert_ok!(TemplateModule::vote(RuntimeOrigin::signed(1), 0, true, 94));
+		// Holds may use frozen funds, but moving them away would leave the vote unbacked.
+		assert_ok!(TemplateModule::create_counter(
+			RuntimeOrigin::signed(1),
+			seq.clone(),
+			0,
+			None,
+			None
+		));
+		assert_noop!(
+			TemplateModule::transfer_ownership(RuntimeOrigin::signed(1), seq.clone(), 2),
+			TokenError::Frozen
+		);
+		assert_ok!(TemplateModule::delete_counter(RuntimeOrigin::signed(1), seq));
+	});
+}
+
+#[test]
+fn revealed_commitment_stores_value() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let salt = [7u8; 32];
+		let hash = TemplateModule::commitment_hash(42, &salt);
+		assert_ok!(TemplateModule::commit(RuntimeOrigin::signed(1), hash));
+		System::assert_last_event(Event::Committed { who: 1, reveal_by: 4 }.into());
+		assert_eq!(Commitments::<Test>::get(1), Some(Commitment { hash, committed_at: 1 }));
+
+		run_to_block(2);
+		assert_ok!(TemplateModule::reveal(RuntimeOrigin::signed(1), 42, salt));
+		System::assert_last_event(Event::SomethingStored { who: 1, old: None, new: 42 }.into());
+		assert_eq!(TemplateModule::something(1), Some(42));
+		assert_eq!(held(1), 10);
+		assert!(!Commitments::<Test>::contains_key(1));
+	});
+}
+
+#[test]
+fn reveal_must_match_commitment_and_window() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let salt = [7u8; 32];
+		assert_noop!(
+			TemplateModule::reveal(RuntimeOrigin::signed(1), 42, salt),
+			Error::<Test>::NoCommitment
+		);
+
+		let hash = TemplateModule::commitment_hash(42, &salt);
+		assert_ok!(TemplateModule::commit(RuntimeOrigin::signed(1), hash));
+		assert_noop!(
+			TemplateModule::reveal(RuntimeOrigin::signed(1), 42, salt),
+			Error::<Test>::RevealTooEarly
+		);
+
+		run_to_block(2);
+		assert_noop!(
+			TemplateModule::reveal(RuntimeOrigin::signed(1), 42, [8u8; 32]),
+			Error::<Test>::InvalidReveal
+		);
+		assert_noop!(
+			TemplateModule::reveal(RuntimeOrigin::signed(1), 43, salt),
+			Error::<Test>::InvalidReveal
+		);
+
+		run_to_block(5);
+		assert_noop!(
+			TemplateModule::reveal(RuntimeOrigin::signed(1), 42, salt),
+			Error::<Test>::RevealTooLate
+		);
+		assert_eq!(TemplateModule::something(1), None);
+	});
+}
+
+#[test]
+fn commitment_can_only_be_replaced_once_expired() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let first = TemplateModule::commitment_hash(1, &[0u8; 32]);
+		let second = TemplateModule::commitment_hash(2, &[0u8; 32]);
+		assert_ok!(TemplateModule::commit(RuntimeOrigin::signed(1), first));
+
+		run_to_block(4);
+		assert_noop!(
+			TemplateModule::commit(RuntimeOrigin::signed(1), second),
+			Error::<Test>::AlreadyCommitted
+		);
+
+		run_to_block(5);
+		assert_ok!(TemplateModule::commit(RuntimeOrigin::signed(1), second));
+		assert_eq!(Commitments::<Test>::get(1), Some(Commitment { hash: second, committed_at: 5 }));
+	});
+}
+
+#[test]
+fn on_idle_removes_expired_commitments() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let hash = TemplateModule::commitment_hash(1, &[0u8; 32]);
+		assert_ok!(TemplateModule::commit(RuntimeOrigin::signed(1), hash));
+		run_to_block(3);
+		assert_ok!(TemplateModule::commit(RuntimeOrigin::signed(2), hash));
+
+		run_to_block(5);
+		TemplateModule::on_idle(5, Weight::MAX);
+		System::assert_last_event(Event::CommitmentExpired { who: 1 }.into());
+		assert!(!Commitments::<Test>::contains_key(1));
+		assert!(Commitments::<Test>::contains_key(2));
+		assert_eq!(CommitmentCursor::<Test>::get(), None);
+	});
+}
+
+#[test]
+fn commitment_expiry_resumes_where_it_ran_out_of_weight() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let hash = TemplateModule::commitment_hash(1, &[0u8; 32]);
+		for who in 1..=3 {
+			assert_ok!(TemplateModule::commit(RuntimeOrigin::signed(who), hash));
+		}
+		run_to_block(5);
+
+		let one = <Test as frame_system::Config>::DbWeight::get().reads_writes(1, 1) +
+			<SubstrateWeight<Test> as WeightInfo>::expire_commitment();
+		assert_eq!(TemplateModule::on_idle(5, one - Weight::from_parts(1, 0)), Weight::zero());
+		assert_eq!(Commitments::<Test>::iter().count(), 3);
+
+		for remaining in (0..3).rev() {
+			assert_eq!(TemplateModule::on_idle(5, one), one);
+			assert_eq!(Commitments::<Test>::iter().count(), remaining);
+			assert!(CommitmentCursor::<Test>::get().is_some());
+		}
+		TemplateModule::on_idle(5, one);
+		assert_eq!(CommitmentCursor::<Test>::get(), None);
+	});
+}
+
+#[test]
+fn feeders_are_managed_by_root() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			TemplateModule::add_feeder(RuntimeOrigin::signed(1), 1),
+			DispatchError::BadOrigin
+		);
+		for who in [3, 1, 4, 2] {
+			assert_ok!(TemplateModule::add_feeder(RuntimeOrigin::root(), who));
+		}
+		System::assert_last_event(Event::FeederAdded { who: 2 }.into());
+		assert_eq!(Feeders::<Test>::get().into_inner(), vec![1, 2, 3, 4]);
+		assert_noop!(
+			TemplateModule::add_feeder(RuntimeOrigin::root(), 1),
+			Error::<Test>::AlreadyFeeder
+		);
+		assert_noop!(
+			TemplateModule::add_feeder(RuntimeOrigin::root(), 5),
+			Error::<Test>::TooManyFeeders
+		);
+
+		assert_ok!(TemplateModule::feed(RuntimeOrigin::signed(2), 7));
+		assert_ok!(TemplateModule::remove_feeder(RuntimeOrigin::root(), 2));
+		System::assert_last_event(Event::FeederRemoved { who: 2 }.into());
+		assert_eq!(Feeders::<Test>::get().into_inner(), vec![1, 3, 4]);
+		assert!(Feeds::<Test>::get().is_empty());
+		assert_noop!(
+			TemplateModule::remove_feeder(RuntimeOrigin::root(), 2),
+			Error::<Test>::NotFeeder
+		);
+		assert_noop!(TemplateModule::feed(RuntimeOrigin::signed(2), 7), Error::<Test>::NotFeeder);
+	});
+}
+
+#[test]
+fn round_stores_median_of_fed_values() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		for who in 1..=3 {
+			assert_ok!(TemplateModule::add_feeder(RuntimeOrigin::root(), who));
+		}
+		assert_ok!(TemplateModule::feed(RuntimeOrigin::signed(1), 10));
+		System::assert_last_event(Event::Fed { who: 1, round: 0, value: 10 }.into());
+		assert_ok!(TemplateModule::feed(RuntimeOrigin::signed(2), 1_000));
+		// A feeder's latest value for the round replaces its earlier ones.
+		assert_ok!(TemplateModule::feed(RuntimeOrigin::signed(2), 12));
+		assert_ok!(TemplateModule::feed(RuntimeOrigin::signed(3), 11));
+
+		run_to_block(10);
+		assert_eq!(TemplateModule::aggregated(), None);
+		run_to_block(11);
+		System::assert_has_event(
+			Event::RoundFinalized { round: 0, value: Some(11), fed: 3, feeders: 3 }.into(),
+		);
+		assert_eq!(TemplateModule::something(OracleAccount::get()), Some(11));
+		assert_eq!(TemplateModule::latest().map(|entry| entry.who), Some(OracleAccount::get()));
+		assert_eq!(
+			Aggregated::<Test>::get(),
+			Some(AggregatedValue { value: 11, round: 0, updated_at: 10 * BLOCK_TIME })
+		);
+		assert_eq!(TemplateModule::aggregated(), Some((11, BLOCK_TIME)));
+		assert_eq!(Round::<Test>::get(), 1);
+		assert!(Feeds::<Test>::get().is_empty());
+		assert_eq!(held(OracleAccount::get()), 0);
+
+		// With an even number of values, the two in the middle are averaged.
+		for (who, value) in [(1, 20), (2, 25), (3, 1), (4, u32::MAX)] {
+			if who == 4 {
+				assert_ok!(TemplateModule::add_feeder(RuntimeOrigin::root(), who));
+			}
+			assert_ok!(TemplateModule::feed(RuntimeOrigin::signed(who), value));
+		}
+		run_to_block(21);
+		System::assert_has_event(
+			Event::RoundFinalized { round: 1, value: Some(22), fed: 4, feeders: 4 }.into(),
+		);
+		assert_eq!(TemplateModule::aggregated(), Some((22, BLOCK_TIME)));
+	});
+}
+
+#[test]
+fn aggregated_value_does_not_expire_with_an_earlier_ttl() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		let oracle = OracleAccount::get();
+		Balances::set_balance(&oracle, INITIAL_BALANCE);
+		// The oracle account set a value of its own, expiring after the round ends.
+		assert_ok!(TemplateModule::do_something_with_ttl(RuntimeOrigin::signed(oracle), 7, 12));
+		assert_ok!(TemplateModule::add_feeder(RuntimeOrigin::root(), 1));
+		assert_ok!(TemplateModule::feed(RuntimeOrigin::signed(1), 42));
+
+		run_to_block(11);
+		assert_eq!(ExpiresAt::<Test>::get(oracle), None);
+		assert!(!Expiring::<Test>::contains_key(13));
+		run_to_block(13);
+		TemplateModule::on_idle(13, Weight::MAX);
+		assert_eq!(TemplateModule::something(oracle), Some(42));
+	});
+}
+
+#[test]
+fn round_without_feeds_keeps_the_last_value() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::add_feeder(RuntimeOrigin::root(), 1));
+		assert_ok!(TemplateModule::feed(RuntimeOrigin::signed(1), 42));
+		run_to_block(11);
+
+		run_to_block(21);
+		System::assert_has_event(
+			Event::RoundFinalized { round: 1, value: None, fed: 0, feeders: 1 }.into(),
+		);
+		assert_eq!(Round::<Test>::get(), 2);
+		assert_eq!(
+			Aggregated::<Test>::get(),
+			Some(AggregatedValue { value: 42, round: 0, updated_at: 10 * BLOCK_TIME })
+		);
+		// The value's age tells clients how stale it is.
+		assert_eq!(TemplateModule::aggregated(), Some((42, 11 * BLOCK_TIME)));
+		// Instances run their own oracles.
+		assert_eq!(SecondTemplate::aggregated(), None);
+		assert_eq!(Round::<Test, Instance2>::get(), 2);
+	});
+}
+
+/// A [`BatchOf`] setting each account's value.
+fn batch(values: &[(u64, u32)]) -> BatchOf<Test> {
+	values.to_vec().try_into().unwrap()
+}
+
+#[test]
+fn set_many_is_checked_like_do_something() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			TemplateModule::set_many(RuntimeOrigin::root(), batch(&[(1, 10)]), BatchMode::Atomic),
+			DispatchError::BadOrigin
+		);
+		SetOriginAccepts::set(SetOriginKind::Allowlist);
+		assert_noop!(
+			TemplateModule::set_many(
+				RuntimeOrigin::signed(1),
+				batch(&[(1, 10)]),
+				BatchMode::Atomic
+			),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn atomic_batch_sets_every_value() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		assert_ok!(TemplateModule::set_many(
+			RuntimeOrigin::signed(1),
+			batch(&[(1, 10), (1, 11)]),
+			BatchMode::Atomic
+		));
+		assert_eq!(TemplateModule::something(1), Some(11));
+		// The deposit is held once, like for repeated `do_something` calls.
+		assert_eq!(held(1), 10);
+		System::assert_has_event(
+			Event::BatchItemSucceeded { index: 0, who: 1, old: None, new: 10 }.into(),
+		);
+		System::assert_last_event(
+			Event::BatchItemSucceeded { index: 1, who: 1, old: Some(10), new: 11 }.into(),
+		);
+	});
+}
+
+#[test]
+fn atomic_batch_is_undone_by_a_value_that_cannot_be_set() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_noop!(
+			TemplateModule::set_many(
+				RuntimeOrigin::signed(1),
+				batch(&[(1, 10), (2, 20), (1, 11)]),
+				BatchMode::Atomic
+			),
+			Error::<Test>::NotOwnValue
+		);
+
+		// Account 6 can't afford the deposit.
+		Balances::set_balance(&6, 5);
+		assert_noop!(
+			TemplateModule::set_many(
+				RuntimeOrigin::signed(6),
+				batch(&[(6, 60)]),
+				BatchMode::Atomic
+			),
+			TokenError::FundsUnavailable
+		);
+	});
+}
+
+#[test]
+fn best_effort_batch_sets_the_values_it_can() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		assert_ok!(TemplateModule::set_many(
+			RuntimeOrigin::signed(1),
+			batch(&[(1, 10), (2, 20), (1, 11)]),
+			BatchMode::BestEffort
+		));
+		assert_eq!(TemplateModule::something(1), Some(11));
+		assert_eq!(TemplateModule::something(2), None);
+		assert_eq!(held(2), 0);
+		System::assert_has_event(
+			Event::BatchItemFailed {
+				index: 1,
+				who: 2,
+				value: 20,
+				error: Error::<Test>::NotOwnValue.into(),
+			}
+			.into(),
+		);
+		System::assert_last_event(
+			Event::BatchItemSucceeded { index: 2, who: 1, old: Some(10), new: 11 }.into(),
+		);
+
+		Balances::set_balance(&6, 5);
+		assert_ok!(TemplateModule::set_many(
+			RuntimeOrigin::signed(6),
+			batch(&[(6, 60)]),
+			BatchMode::BestEffort
+		));
+		assert_eq!(TemplateModule::something(6), None);
+		assert_eq!(held(6), 0);
+		System::assert_last_event(
+			Event::BatchItemFailed {
+				index: 0,
+				who: 6,
+				value: 60,
+				error: TokenError::FundsUnavailable.into(),
+			}
+			.into(),
+		);
+	});
+}
+
+/// The deposit held for proposals of the default pallet instance.
+fn proposal_held(who: u64) -> u64 {
+	Balances::balance_on_hold(&RuntimeHoldReason::TemplateModule(HoldReason::ProposalDeposit), &who)
+}
+
+/// The balance frozen by votes in the default pallet instance.
+fn frozen(who: u64) -> u64 {
+	Balances::balance_frozen(&RuntimeFreezeReason::TemplateModule(FreezeReason::Vote), &who)
+}
+
+#[test]
+fn propose_holds_a_deposit_until_the_vote_ends() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		assert_ok!(TemplateModule::propose(RuntimeOrigin::signed(1), 42));
+		System::assert_last_event(
+			Event::Proposed { index: 0, proposer: 1, value: 42, end: 4 }.into(),
+		);
+		assert_eq!(proposal_held(1), 10);
+		assert_eq!(ProposalsEnding::<Test>::get(4).into_inner(), vec![0]);
+
+		// Nobody voted, so the proposal is rejected at the start of block 4.
+		run_to_block(3);
+		assert!(Proposals::<Test>::contains_key(0));
+		run_to_block(4);
+		System::assert_last_event(Event::ProposalRejected { index: 0, ayes: 0, nays: 0 }.into());
+		assert!(!Proposals::<Test>::contains_key(0));
+		assert_eq!(proposal_held(1), 0);
+		assert_eq!(TemplateModule::something(ProposalAccount::get()), None);
+	});
+}
+
+#[test]
+fn proposal_indices_do_not_wrap_around() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		ProposalCount::<Test>::put(ProposalIndex::MAX);
+
+		// The last index would reuse the first one's, so no proposal gets it.
+		assert_noop!(
+			TemplateModule::propose(RuntimeOrigin::signed(1), 42),
+			Error::<Test>::ProposalCountOverflow
+		);
+		assert_eq!(proposal_held(1), 0);
+	});
+}
+
+#[test]
+fn proposals_are_limited_and_paid_for() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+
+		assert_noop!(
+			TemplateModule::propose(RuntimeOrigin::signed(7), 42),
+			TokenError::FundsUnavailable
+		);
+		assert_ok!(TemplateModule::propose(RuntimeOrigin::signed(1), 1));
+		assert_ok!(TemplateModule::propose(RuntimeOrigin::signed(1), 2));
+		assert_noop!(
+			TemplateModule::propose(RuntimeOrigin::signed(2), 3),
+			Error::<Test>::TooManyProposals
+		);
+		assert_eq!(proposal_held(1), 20);
+
+		// Closing the votes frees the slots.
+		run_to_block(4);
+		assert_ok!(TemplateModule::propose(RuntimeOrigin::signed(2), 3));
+		assert_eq!(proposal_held(1), 0);
+	});
+}
+
+#[test]
+fn votes_freeze_the_balance_voted_with() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		// Account 3's value deposit is held, leaving it 90 free, of which it keeps the existential
+		// deposit and the fee margin.
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(3), 1));
+		assert_ok!(TemplateModule::propose(RuntimeOrigin::signed(1), 42));
+
+		assert_ok!(TemplateModule::vote(RuntimeOrigin::signed(2), 0, true, 60));
+		System::assert_last_event(
+			Event::Voted { index: 0, who: 2, aye: true, balance: 60, ayes: 60, nays: 0 }.into(),
+		);
+		assert_noop!(
+			TemplateModule::vote(RuntimeOrigin::signed(3), 0, false, 85),
+			Error::<Test>::InsufficientVotingBalance
+		);
+		assert_ok!(TemplateModule::vote(RuntimeOrigin::signed(3), 0, false, 84));
+		System::assert_last_event(
+			Event::Voted { index: 0, who: 3, aye: false, balance: 84, ayes: 60, nays: 84 }.into(),
+		);
+		assert_eq!(Votes::<Test>::get(0, 3), Some(Vote { aye: false, balance: 84 }));
+		assert_eq!((frozen(2), frozen(3)), (60, 84));
+		// Only the balance voted with is frozen.
+		assert_ok!(<Balances as Mutate<u64>>::transfer(&2, &4, 40, Preservation::Expendable));
+		assert_noop!(
+			<Balances as Mutate<u64>>::transfer(&2, &4, 1, Preservation::Expendable),
+			TokenError::Frozen
+		);
+
+		assert_noop!(
+			TemplateModule::vote(RuntimeOrigin::signed(2), 0, false, 1),
+			Error::<Test>::AlreadyVoted
+		);
+		assert_noop!(
+			TemplateModule::vote(RuntimeOrigin::signed(2), 1, true, 1),
+			Error::<Test>::UnknownProposal
+		);
+		assert_noop!(
+			TemplateModule::vote(RuntimeOrigin::signed(4), 0, true, 0),
+			Error::<Test>::NoVotingBalance
+		);
+		assert_noop!(
+			TemplateModule::vote(RuntimeOrigin::signed(7), 0, true, 1),
+			Error::<Test>::InsufficientVotingBalance
+		);
+		assert_ok!(TemplateModule::vote(RuntimeOrigin::signed(4), 0, true, 94));
+		assert_noop!(
+			TemplateModule::vote(RuntimeOrigin::signed(5), 0, true, 94),
+			Error::<Test>::TooManyVoters
+		);
+	});
+}
+
+#[test]
+fn voter_can_pay_fees_while_its_vote_is_live() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::propose(RuntimeOrigin::signed(1), 42));
+		assert_ok!(TemplateModule::vote(RuntimeOrigin::signed(2), 0, true, 94));
+		assert_eq!(frozen(2), 94);
+
+		// The fee margin stays spendable for transaction fees.
+		assert_ok!(<Balances as frame_support::traits::Currency<u64>>::withdraw(
+			&2,
+			5,
+			WithdrawReasons::TRANSACTION_PAYMENT,
+			ExistenceRequirement::KeepAlive,
+		));
+		assert_eq!(Balances::free_balance(2), 95);
+		assert_eq!(frozen(2), 94);
+	});
+}
+
+#[test]
+fn passed_proposal_is_enacted_and_unfreezes_its_voters() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::propose(RuntimeOrigin::signed(1), 42));
+		assert_ok!(TemplateModule::vote(RuntimeOrigin::signed(2), 0, true, 94));
+		assert_ok!(TemplateModule::vote(RuntimeOrigin::signed(3), 0, false, 94));
+
+		// A tie reaches the 50% threshold.
+		run_to_block(4);
+		System::assert_has_event(
+			Event::ProposalPassed { index: 0, value: 42, ayes: 94, nays: 94 }.into(),
+		);
+		assert_eq!(TemplateModule::something(ProposalAccount::get()), Some(42));
+		assert_eq!(TemplateModule::latest().map(|entry| entry.value), Some(42));
+		assert_eq!((frozen(2), frozen(3)), (0, 0));
+		assert_eq!(VoteLocks::<Test>::get(2).len(), 0);
+		assert_eq!(Votes::<Test>::iter_prefix(0).count(), 0);
+		assert_ok!(<Balances as Mutate<u64>>::transfer(&2, &4, 50, Preservation::Expendable));
+	});
+}
+
+#[test]
+fn passed_proposal_does_not_expire_with_an_earlier_ttl() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		let account = ProposalAccount::get();
+		Balances::set_balance(&account, INITIAL_BALANCE);
+		// The proposal account set a value of its own, expiring after the vote ends.
+		assert_ok!(TemplateModule::do_something_with_ttl(RuntimeOrigin::signed(account), 7, 5));
+		assert_ok!(TemplateModule::propose(RuntimeOrigin::signed(1), 42));
+		assert_ok!(TemplateModule::vote(RuntimeOrigin::signed(2), 0, true, 94));
+
+		run_to_block(4);
+		assert_eq!(ExpiresAt::<Test>::get(account), None);
+		assert!(!Expiring::<Test>::contains_key(6));
+		run_to_block(6);
+		TemplateModule::on_idle(6, Weight::MAX);
+		assert_eq!(TemplateModule::something(account), Some(42));
+	});
+}
+
+#[test]
+fn proposal_without_enough_ayes_is_rejected() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		Balances::set_balance(&2, 50);
+		assert_ok!(TemplateModule::propose(RuntimeOrigin::signed(1), 42));
+		assert_ok!(TemplateModule::vote(RuntimeOrigin::signed(2), 0, true, 44));
+		assert_ok!(TemplateModule::vote(RuntimeOrigin::signed(3), 0, false, 94));
+
+		run_to_block(4);
+		System::assert_last_event(Event::ProposalRejected { index: 0, ayes: 44, nays: 94 }.into());
+		assert_eq!(TemplateModule::something(ProposalAccount::get()), None);
+		assert_eq!((frozen(2), frozen(3)), (0, 0));
+	});
+}
+
+#[test]
+fn votes_stay_frozen_until_the_last_vote_ends() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(TemplateModule::propose(RuntimeOrigin::signed(1), 1));
+		assert_ok!(TemplateModule::vote(RuntimeOrigin::signed(2), 0, true, 94));
+		run_to_block(2);
+		assert_ok!(TemplateModule::propose(RuntimeOrigin::signed(1), 2));
+		// Account 2 votes on the second proposal with less after paying a value deposit.
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(2), 7));
+		assert_ok!(TemplateModule::vote(RuntimeOrigin::signed(2), 1, false, 84));
+		assert_eq!(VoteLocks::<Test>::get(2).into_inner(), vec![(0, 94), (1, 84)]);
+		assert_eq!(frozen(2), 94);
+
+		// The first vote ends and account 2 stays frozen for the second.
+		run_to_block(4);
+		assert_eq!(VoteLocks::<Test>::get(2).into_inner(), vec![(1, 84)]);
+		assert_eq!(frozen(2), 84);
+		run_to_block(5);
+		assert_eq!(frozen(2), 0);
+		assert!(!VoteLocks::<Test>::contains_key(2));
+	});
+}
+
+#[test]
+fn second_instance_proposals_are_separate() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		assert_ok!(SecondTemplate::propose(RuntimeOrigin::signed(1), 42));
+		assert_ok!(SecondTemplate::vote(RuntimeOrigin::signed(2), 0, true, 94));
+		assert_noop!(
+			TemplateModule::vote(RuntimeOrigin::signed(2), 0, true, 94),
+			Error::<Test>::UnknownProposal
+		);
+
+		run_to_block(4);
+		assert_eq!(SecondTemplate::something(SecondProposalAccount::get()), Some(42));
+		assert_eq!(TemplateModule::something(ProposalAccount::get()), None);
+	});
+}
+
+/// A [`Call::submit_value_unsigned`] for `value`, signed by `key`.
+fn unsigned_submission(key: u64, block_number: u64, value: u32) -> Call<Test> {
+	let payload = ValuePayload { block_number, public: UintAuthorityId(key), value };
+	let signature = TestSignature(key, payload.encode());
+	Call::submit_value_unsigned { payload, signature }
+}
+
+#[test]
+fn offchain_worker_submits_fetched_value_signed() {
+	let (mut t, offchain_state, pool_state) = new_offchain_test_ext();
+	offchain_state.write().expect_request(PendingRequest {
+		method: "GET".into(),
+		uri: "http://localhost:8000/value".into(),
+		response: Some(b"42\n".to_vec()),
+		sent: true,
+		..Default::default()
+	});
+	UintAuthorityId::set_all_keys(vec![1u64]);
+
+	t.execute_with(|| {
+		StorageValueRef::persistent(&TemplateModule::offchain_key(offchain::OCW_URL_KEY))
+			.set(&b"http://localhost:8000/value".to_vec());
+		TemplateModule::offchain_worker(1);
+
+		let tx = pool_state.write().transactions.pop().unwrap();
+		assert!(pool_state.read().transactions.is_empty());
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		assert_eq!(tx.signature, Some((1, ())));
+		assert_eq!(tx.call, RuntimeCall::TemplateModule(Call::do_something { something: 42 }));
+	});
+}
+
+#[test]
+fn offchain_worker_submits_signed_once_per_interval_and_value() {
+	let (mut t, _, pool_state) = new_offchain_test_ext();
+	UintAuthorityId::set_all_keys(vec![1u64]);
+
+	t.execute_with(|| {
+		let value =
+			StorageValueRef::persistent(&TemplateModule::offchain_key(offchain::OCW_VALUE_KEY));
+		value.set(&7u32);
+		TemplateModule::offchain_worker(1);
+		TemplateModule::offchain_worker(2);
+		assert_eq!(pool_state.read().transactions.len(), 1);
+
+		// A new value waits for the interval to pass.
+		value.set(&8u32);
+		TemplateModule::offchain_worker(5);
+		assert_eq!(pool_state.read().transactions.len(), 1);
+		TemplateModule::offchain_worker(6);
+		assert_eq!(pool_state.read().transactions.len(), 2);
+		let tx = Extrinsic::decode(&mut &*pool_state.read().transactions[1]).unwrap();
+		assert_eq!(tx.call, RuntimeCall::TemplateModule(Call::do_something { something: 8 }));
+
+		// An unchanged value is not submitted again.
+		TemplateModule::offchain_worker(11);
+		assert_eq!(pool_state.read().transactions.len(), 2);
+	});
+}
+
+#[test]
+fn offchain_worker_submits_configured_value_unsigned() {
+	let (mut t, _, pool_state) = new_offchain_test_ext();
+	UintAuthorityId::set_all_keys(vec![1u64]);
+
+	t.execute_with(|| {
+		System::set_block_number(1);
+		StorageValueRef::persistent(&TemplateModule::offchain_key(offchain::OCW_VALUE_KEY))
+			.set(&7u32);
+		StorageValueRef::persistent(&TemplateModule::offchain_key(offchain::OCW_UNSIGNED_KEY))
+			.set(&true);
+		TemplateModule::offchain_worker(1);
+
+		let tx = pool_state.write().transactions.pop().unwrap();
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		assert_eq!(tx.signature, None);
+		assert_eq!(tx.call, RuntimeCall::TemplateModule(unsigned_submission(1, 1, 7)));
+
+		// Nothing is submitted before the unsigned interval has passed.
+		NextUnsignedAt::<Test>::put(6);
+		TemplateModule::offchain_worker(5);
+		assert!(pool_state.read().transactions.is_empty());
+	});
+}
+
+#[test]
+fn offchain_worker_fetches_nothing_without_a_key_or_url() {
+	let (mut t, offchain_state, pool_state) = new_offchain_test_ext();
+
+	t.execute_with(|| {
+		// Without a key nothing is read, even with a URL configured.
+		UintAuthorityId::set_all_keys(Vec::<u64>::new());
+		StorageValueRef::persistent(&TemplateModule::offchain_key(offchain::OCW_URL_KEY))
+			.set(&b"http://localhost:8000/value".to_vec());
+		TemplateModule::offchain_worker(1);
+		assert!(offchain_state.read().requests.is_empty());
+
+		// Without a URL nothing is fetched, even with a key.
+		UintAuthorityId::set_all_keys(vec![1u64]);
+		StorageValueRef::persistent(&TemplateModule::offchain_key(offchain::OCW_URL_KEY)).clear();
+		TemplateModule::offchain_worker(1);
+		assert!(offchain_state.read().requests.is_empty());
+
+		assert!(pool_state.read().transactions.is_empty());
+	});
+}
+
+#[test]
+fn offchain_worker_submits_nothing_for_a_bad_response() {
+	let (mut t, offchain_state, pool_state) = new_offchain_test_ext();
+	offchain_state.write().expect_request(PendingRequest {
+		method: "GET".into(),
+		uri: "http://localhost:9000/price".into(),
+		response: Some(b"not a number".to_vec()),
+		sent: true,
+		..Default::default()
+	});
+	UintAuthorityId::set_all_keys(vec![1u64]);
+
+	t.execute_with(|| {
+		StorageValueRef::persistent(&TemplateModule::offchain_key(offchain::OCW_URL_KEY))
+			.set(&b"http://localhost:9000/price".to_vec());
+		TemplateModule::offchain_worker(1);
+
+		assert!(pool_state.read().transactions.is_empty());
+	});
+}
+
+#[test]
+fn unsigned_submission_is_validated() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(5);
+		let validate = |call: &Call<Test>| {
+			TemplateModule::validate_unsigned(TransactionSource::External, call)
+		};
+
+		// Only writers may submit values for free.
+		assert_eq!(validate(&unsigned_submission(1, 5, 42)), InvalidTransaction::BadSigner.into());
+		assert_ok!(TemplateModule::add_writer(RuntimeOrigin::root(), 1));
+		assert!(validate(&unsigned_submission(1, 5, 42)).is_ok());
+
+		// A writer that can't pay the deposit is turned away before it takes a block's slot.
+		assert_ok!(TemplateModule::add_writer(RuntimeOrigin::root(), 6));
+		assert_eq!(validate(&unsigned_submission(6, 5, 42)), InvalidTransaction::Payment.into());
+
+		// The payload must be signed by its own key.
+		let Call::submit_value_unsigned { payload, .. } = unsigned_submission(1, 5, 42) else {
+			unreachable!()
+		};
+		let forged =
+			Call::submit_value_unsigned { signature: TestSignature(2, payload.encode()), payload };
+		assert_eq!(validate(&forged), InvalidTransaction::BadProof.into());
+
+		assert_eq!(validate(&unsigned_submission(1, 6, 42)), InvalidTransaction::Future.into());
+		NextUnsignedAt::<Test>::put(5);
+		assert_eq!(validate(&unsigned_submission(1, 4, 42)), InvalidTransaction::Stale.into());
+
+		// The signer must be accepted by `SetOrigin`.
+		SetOriginAccepts::set(SetOriginKind::Root);
+		assert_eq!(validate(&unsigned_submission(1, 5, 42)), InvalidTransaction::BadSigner.into());
+	});
+}
+
+#[test]
+fn unsigned_submission_stores_value_for_signer() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let Call::submit_value_unsigned { payload, signature } = unsigned_submission(1, 1, 42)
+		else {
+			unreachable!()
+		};
+		assert_noop!(
+			TemplateModule::submit_value_unsigned(
+				RuntimeOrigin::signed(1),
+				payload.clone(),
+				signature.clone()
+			),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			TemplateModule::submit_value_unsigned(
+				RuntimeOrigin::none(),
+				payload.clone(),
+				signature.clone()
+			),
+			Error::<Test>::NotWriter
+		);
+		assert_ok!(TemplateModule::add_writer(RuntimeOrigin::root(), 1));
+
+		assert_ok!(TemplateModule::submit_value_unsigned(
+			RuntimeOrigin::none(),
+			payload,
+			signature
+		));
+		assert_eq!(TemplateModule::something(1), Some(42));
+		assert_eq!(held(1), 10);
+		assert_eq!(NextUnsignedAt::<Test>::get(), 6);
+		System::assert_last_event(Event::SomethingStored { who: 1, old: None, new: 42 }.into());
+	});
+}
+
+#[test]
+fn migration_moves_global_value_to_owner() {
+	new_test_ext().execute_with(|| {
+		migrations::v1::Something::<Test, ()>::put(42);
+		assert_eq!(TemplateModule::on_chain_storage_version(), 0);
+
+		migrations::v1::MigrateToPerAccount::<Test, Owner>::on_runtime_upgrade();
+
+		assert_eq!(migrations::v1::Something::<Test, ()>::get(), None);
+		assert_eq!(Something::<Test>::get(5), Some(42));
+		assert_eq!(Something::<Test>::iter().count(), 1);
+		assert_eq!(TemplateModule::on_chain_storage_version(), 1);
+		// The moved value is paid for and recorded like any other.
+		assert_eq!(held(5), 10);
+		assert_eq!(
+			History::<Test>::get().into_inner(),
+			vec![HistoryEntry { block: 0, who: 5, value: 42 }]
+		);
+	});
+}
+
+#[test]
+fn migration_drops_global_value_if_owner_cannot_pay_the_deposit() {
+	new_test_ext().execute_with(|| {
+		migrations::v1::Something::<Test, ()>::put(42);
+
+		migrations::v1::MigrateToPerAccount::<Test, PoorOwner>::on_runtime_upgrade();
+
+		assert_eq!(migrations::v1::Something::<Test, ()>::get(), None);
+		assert_eq!(Something::<Test>::iter().count(), 0);
+		assert!(History::<Test>::get().is_empty());
+		assert_eq!(held(6), 0);
+		assert_eq!(TemplateModule::on_chain_storage_version(), 1);
+	});
+}
+
+#[test]
+fn migration_drops_global_value_without_owner() {
+	new_test_ext().execute_with(|| {
+		migrations::v1::Something::<Test, ()>::put(42);
+
+		migrations::v1::MigrateToPerAccount::<Test, NoOwner>::on_runtime_upgrade();
+
+		assert_eq!(migrations::v1::Something::<Test, ()>::get(), None);
+		assert_eq!(Something::<Test>::iter().count(), 0);
+		assert_eq!(TemplateModule::on_chain_storage_version(), 1);
+	});
+}
+
+#[test]
+fn migration_is_skipped_on_other_storage_versions() {
+	new_test_ext().execute_with(|| {
+		StorageVersion::new(1).put::<TemplateModule>();
+		migrations::v1::Something::<Test, ()>::put(42);
+
+		migrations::v1::MigrateToPerAccount::<Test, Owner>::on_runtime_upgrade();
+
+		assert_eq!(migrations::v1::Something::<Test, ()>::get(), Some(42));
+		assert_eq!(Something::<Test>::iter().count(), 0);
+		assert_eq!(TemplateModule::on_chain_storage_version(), 1);
+	});
+}
+
+#[cfg(feature = "try-runtime")]
+#[test]
+fn migration_passes_try_runtime_checks() {
+	new_test_ext().execute_with(|| {
+		migrations::v1::Something::<Test, ()>::put(42);
+
+		let state = migrations::v1::MigrateToPerAccount::<Test, Owner>::pre_upgrade().unwrap();
+		migrations::v1::MigrateToPerAccount::<Test, Owner>::on_runtime_upgrade();
+		assert_ok!(migrations::v1::MigrateToPerAccount::<Test, Owner>::post_upgrade(state));
+	});
+}
+
+#[test]
+fn writes_are_weighed_for_the_configured_history_and_name_length() {
+	// The default instance keeps 3 history entries and the second one 5.
+	let first = Call::<Test>::do_something { something: 1 }.get_dispatch_info().weight;
+	let second = Call::<Test, Instance2>::do_something { something: 1 }.get_dispatch_info().weight;
+	assert_eq!(first, <SubstrateWeight<Test> as WeightInfo>::do_something(3));
+	assert!(first.ref_time() < second.ref_time());
+
+	let short = Call::<Test>::increment { name: counter_name(b"a"), by: 1 };
+	let long = Call::<Test>::increment { name: counter_name(b"abcdefgh"), by: 1 };
+	assert_eq!(
+		short.get_dispatch_info().weight,
+		<SubstrateWeight<Test> as WeightInfo>::increment(1)
+	);
+	assert!(
+		short.get_dispatch_info().weight.ref_time() < long.get_dispatch_info().weight.ref_time()
+	);
+}
+
+/// The fee [`ChargeFee`] charges.
+const FEE: u64 = 5;
+
+/// Stands in for `ChargeTransactionPayment`, which the mock runtime lacks: burns a fixed fee,
+/// failing with `InvalidTransaction::Payment` when the sender cannot pay it.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, Debug, scale_info::TypeInfo)]
+struct ChargeFee;
+
+impl SignedExtension for ChargeFee {
+	const IDENTIFIER: &'static str = "ChargeFee";
+	type AccountId = u64;
+	type Call = RuntimeCall;
+	type AdditionalSigned = ();
+	type Pre = ();
+
+	fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+		Ok(())
+	}
+
+	fn validate(
+		&self,
+		who: &u64,
+		_call: &RuntimeCall,
+		_info: &DispatchInfoOf<RuntimeCall>,
+		_len: usize,
+	) -> TransactionValidity {
+		if Balances::balance(who) < FEE + 1 {
+			return InvalidTransaction::Payment.into()
+		}
+		Ok(ValidTransaction::default())
+	}
+
+	fn pre_dispatch(
+		self,
+		who: &u64,
+		call: &RuntimeCall,
+		info: &DispatchInfoOf<RuntimeCall>,
+		len: usize,
+	) -> Result<(), TransactionValidityError> {
+		self.validate(who, call, info, len)?;
+		Balances::set_balance(who, Balances::balance(who) - FEE);
+		Ok(())
+	}
+}
+
+type Quota = FreeCallQuota<Test, ChargeFee>;
+
+/// Validate `call` from `who` as the transaction pool would, through [`FreeCallQuota`].
+fn validate_quota(who: u64, call: &RuntimeCall) -> TransactionValidity {
+	Quota::from(ChargeFee).validate(&who, call, &call.get_dispatch_info(), 0)
+}
+
+/// Check `call` from `who` before dispatching it in a block, returning whether it was free.
+fn pre_dispatch_quota(who: u64, call: &RuntimeCall) -> Result<bool, TransactionValidityError> {
+	Quota::from(ChargeFee)
+		.pre_dispatch(&who, call, &call.get_dispatch_info(), 0)
+		.map(|pre| matches!(pre, FreeCallPre::Free { .. }))
+}
+
+/// Apply `call` from `who` in a block as the executive would, through [`FreeCallQuota`],
+/// returning whether it was free.
+fn apply_quota(who: u64, call: RuntimeCall) -> Result<bool, TransactionValidityError> {
+	let info = call.get_dispatch_info();
+	let pre = Quota::from(ChargeFee).pre_dispatch(&who, &call, &info, 0)?;
+	let free = matches!(pre, FreeCallPre::Free { .. });
+	let (post_info, result) = match call.dispatch(RuntimeOrigin::signed(who)) {
+		Ok(post_info) => (post_info, Ok(())),
+		Err(error) => (error.post_info, Err(error.error)),
+	};
+	Quota::post_dispatch(Some(pre), &info, &post_info, 0, &result)?;
+	Ok(free)
+}
+
+fn template_call() -> RuntimeCall {
+	RuntimeCall::TemplateModule(Call::clear_something {})
+}
+
+#[test]
+fn free_calls_are_limited_per_account_and_era() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		// Account 7 holds no funds, account 1 does. Each gets 2 free calls per era.
+		for who in [7, 1] {
+			for used in 1..=2 {
+				assert_ok!(validate_quota(who, &template_call()));
+				assert_eq!(pre_dispatch_quota(who, &template_call()), Ok(true));
+				assert_eq!(FreeCallsUsed::<Test>::get(who), Some((0, used)));
+			}
+		}
+		assert_eq!(Balances::balance(&1), INITIAL_BALANCE);
+
+		// Past the quota, calls are charged, and rejected when they can't be paid for.
+		let exhausted = InvalidTransaction::Custom(FREE_QUOTA_EXHAUSTED).into();
+		assert_eq!(validate_quota(7, &template_call()), Err(exhausted));
+		assert_eq!(pre_dispatch_quota(7, &template_call()), Err(exhausted));
+		assert_ok!(validate_quota(1, &template_call()));
+		assert_eq!(pre_dispatch_quota(1, &template_call()), Ok(false));
+		assert_eq!(Balances::balance(&1), INITIAL_BALANCE - FEE);
+		assert_eq!(FreeCallsUsed::<Test>::get(1), Some((0, 2)));
+	});
+}
+
+#[test]
+fn free_calls_reset_in_the_next_era() {
+	new_test_ext().execute_with(|| {
+		run_to_block(9);
+		for _ in 0..2 {
+			assert_eq!(pre_dispatch_quota(7, &template_call()), Ok(true));
+		}
+		assert!(validate_quota(7, &template_call()).is_err());
+
+		// Block 10 starts the second era of 10 blocks.
+		run_to_block(10);
+		assert_ok!(validate_quota(7, &template_call()));
+		assert_eq!(pre_dispatch_quota(7, &template_call()), Ok(true));
+		assert_eq!(FreeCallsUsed::<Test>::get(7), Some((1, 1)));
+	});
+}
+
+#[test]
+fn free_calls_are_valid_until_the_end_of_their_era() {
+	new_test_ext().execute_with(|| {
+		run_to_block(7);
+		assert_eq!(validate_quota(7, &template_call()).unwrap().longevity, 3);
+	});
+}
+
+#[test]
+fn only_calls_writing_a_value_are_free() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		let propose = RuntimeCall::TemplateModule(Call::propose { value: 1 });
+		assert_eq!(validate_quota(7, &propose), InvalidTransaction::Payment.into());
+		assert_eq!(pre_dispatch_quota(1, &propose), Ok(false));
+		assert_eq!(Balances::balance(&1), INITIAL_BALANCE - FEE);
+		assert_eq!(FreeCallsUsed::<Test>::get(1), None);
+
+		let set = RuntimeCall::TemplateModule(Call::do_something { something: 1 });
+		assert_eq!(pre_dispatch_quota(1, &set), Ok(true));
+		assert_eq!(FreeCallsUsed::<Test>::get(1), Some((0, 1)));
+	});
+}
+
+#[test]
+fn failed_free_call_uses_up_the_quota() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		let set = || RuntimeCall::TemplateModule(Call::do_something { something: 1 });
+		assert_eq!(apply_quota(2, set()), Ok(true));
+		assert_eq!(FreeCallsUsed::<Test>::get(2), Some((0, 1)));
+
+		// Account 1 has no value to clear, so its first free call fails, leaving it none.
+		assert_eq!(apply_quota(1, template_call()), Ok(true));
+		assert_eq!(FreeCallsUsed::<Test>::get(1), Some((0, 2)));
+		assert_eq!(apply_quota(1, set()), Ok(false));
+		assert_eq!(Balances::balance(&1), INITIAL_BALANCE - FEE - 10);
+		assert_eq!(TemplateModule::something(1), Some(1));
+	});
+}
+
+#[test]
+fn only_calls_to_the_instance_are_free() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		let other = RuntimeCall::SecondTemplate(Call::clear_something {});
+		assert_eq!(validate_quota(7, &other), InvalidTransaction::Payment.into());
+		assert_eq!(pre_dispatch_quota(1, &other), Ok(false));
+		assert_eq!(Balances::balance(&1), INITIAL_BALANCE - FEE);
+		assert_eq!(FreeCallsUsed::<Test>::get(1), None);
+	});
+}
+
+/// Models how the transaction pool admits `call` from `who`: it is validated against the current
+/// state and rejected if it fails or provides a tag a pending transaction already provides.
+fn submit_to_pool(
+	pending: &mut Vec<Vec<u8>>,
+	who: u64,
+	call: &RuntimeCall,
+) -> Result<(), TransactionValidityError> {
+	let valid = validate_quota(who, call)?;
+	if valid.provides.iter().any(|tag| pending.contains(tag)) {
+		// What the pool reports as `TooLowPriority` for an equal-priority replacement.
+		return Err(InvalidTransaction::Stale.into())
+	}
+	pending.extend(valid.provides);
+	Ok(())
+}
+
+#[test]
+fn pool_keeps_one_pending_call_per_free_slot() {
+	new_test_ext().execute_with(|| {
+		run_to_block(1);
+		let mut pending = Vec::new();
+		assert_ok!(submit_to_pool(&mut pending, 7, &template_call()));
+		// A second free call validated against the same count would claim the same slot.
+		assert!(submit_to_pool(&mut pending, 7, &template_call()).is_err());
+		// Other accounts have slots of their own.
+		assert_ok!(submit_to_pool(&mut pending, 8, &template_call()));
+
+		// Including the pending call moves the sender on to its next slot.
+		assert_eq!(pre_dispatch_quota(7, &template_call()), Ok(true));
+		pending.clear();
+		assert_ok!(submit_to_pool(&mut pending, 7, &template_call()));
+		assert_eq!(pre_dispatch_quota(7, &template_call()), Ok(true));
+
+		// With the quota used up the pool turns away calls the sender cannot pay for.
+		pending.clear();
+		assert_eq!(
+			submit_to_pool(&mut pending, 7, &template_call()),
+			Err(InvalidTransaction::Custom(FREE_QUOTA_EXHAUSTED).into())
+		);
+		assert!(pending.is_empty());
+	});
+}
@@ -0,0 +1,114 @@
+//! Types used by the template pallet's storage, events and runtime API.
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use scale_info::TypeInfo;
+use sp_runtime::RuntimeDebug;
+
+/// A value written to the pallet, as recorded in its history.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct HistoryEntry<BlockNumber, AccountId> {
+	/// The block in which the value was written.
+	pub block: BlockNumber,
+	/// The account that wrote the value.
+	pub who: AccountId,
+	/// The value written.
+	pub value: u32,
+}
+
+/// A value change waiting to be applied at the start of a future block.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct ScheduledChange<AccountId> {
+	/// The account the value will be set for.
+	pub who: AccountId,
+	/// The value to set.
+	pub value: u32,
+}
+
+/// A named counter in the pallet's counter registry.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct CounterInfo<AccountId, Balance> {
+	/// The account allowed to change the counter.
+	pub owner: AccountId,
+	/// The deposit held from the owner until the counter is deleted.
+	pub deposit: Balance,
+	/// The counter's current value.
+	pub value: u64,
+	/// The lowest value the counter may take, if any.
+	pub min: Option<u64>,
+	/// The highest value the counter may take, if any.
+	pub max: Option<u64>,
+}
+
+impl<AccountId, Balance> CounterInfo<AccountId, Balance> {
+	/// Whether `value` lies within the counter's bounds.
+	pub fn allows(&self, value: u64) -> bool {
+		self.min.map_or(true, |min| value >= min) && self.max.map_or(true, |max| value <= max)
+	}
+
+	/// The value the counter is reset to: its lower bound, or zero without one.
+	pub fn reset_value(&self) -> u64 {
+		self.min.unwrap_or(0)
+	}
+}
+
+/// A hidden value an account has committed to and may later reveal.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Commitment<BlockNumber> {
+	/// `blake2_256` of the SCALE-encoded value followed by the salt.
+	pub hash: [u8; 32],
+	/// The block in which the commitment was made.
+	pub committed_at: BlockNumber,
+}
+
+/// The value the pallet's feeders agreed on in a round.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct AggregatedValue<Moment> {
+	/// The median of the values fed in the round.
+	pub value: u32,
+	/// The round the value was aggregated in.
+	pub round: u32,
+	/// When the round was finalized.
+	pub updated_at: Moment,
+}
+
+/// How [`Pallet::set_many`](crate::Pallet::set_many) handles values that can't be set.
+#[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum BatchMode {
+	/// Set every value or none: the first value that can't be set undoes the batch and fails the
+	/// call.
+	Atomic,
+	/// Set every value that can be set, reporting the outcome of each one in an event.
+	BestEffort,
+}
+
+/// A proposal to store a value under the pallet's proposal account, open for votes.
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Proposal<AccountId, Balance, BlockNumber> {
+	/// The account that made the proposal.
+	pub proposer: AccountId,
+	/// The value to store if the proposal passes.
+	pub value: u32,
+	/// The deposit held from the proposer until the vote ends.
+	pub deposit: Balance,
+	/// The block at the start of which the vote ends.
+	pub end: BlockNumber,
+	/// The total balance voting for the proposal.
+	pub ayes: Balance,
+	/// The total balance voting against the proposal.
+	pub nays: Balance,
+	/// The number of accounts that voted.
+	pub voters: u32,
+}
+
+/// An account's vote on a [`Proposal`].
+#[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct Vote<Balance> {
+	/// Whether the vote is for the proposal.
+	pub aye: bool,
+	/// The balance voted with, frozen until the vote ends.
+	pub balance: Balance,
+}
@@ -0,0 +1,68 @@
+//! Benchmarking setup for pallet-validator-set
+#![cfg(feature = "runtime-benchmarks")]
+use super::*;
+use sp_std::vec::Vec;
+
+#[allow(unused)]
+use crate::Pallet as ValidatorSet;
+use frame_benchmarking::v2::*;
+use frame_support::{
+	traits::{EnsureOrigin, Get},
+	BoundedVec,
+};
+
+/// Fill [`Validators`] with `n` accounts.
+fn fill_validators<T: Config>(n: u32) -> Vec<T::AccountId> {
+	let validators = (0..n).map(|i| account("validator", i, 0)).collect::<Vec<T::AccountId>>();
+	Validators::<T>::put(BoundedVec::truncate_from(validators.clone()));
+	validators
+}
+
+#[benchmarks]
+mod benchmarks {
+	use super::*;
+
+	#[benchmark]
+	fn add_validator() -> Result<(), BenchmarkError> {
+		fill_validators::<T>(T::MaxValidators::get() - 1);
+		let origin =
+			T::AddRemoveOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+		let who: T::AccountId = account("new", 0, 0);
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, who.clone());
+
+		assert!(Validators::<T>::get().contains(&who));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn remove_validator() -> Result<(), BenchmarkError> {
+		// The last validator is the slowest to find.
+		let who = fill_validators::<T>(T::MaxValidators::get())
+			.pop()
+			.expect("`MaxValidators` is not zero");
+		let origin =
+			T::AddRemoveOrigin::try_successful_origin().map_err(|_| BenchmarkError::Weightless)?;
+
+		#[extrinsic_call]
+		_(origin as T::RuntimeOrigin, who.clone());
+
+		assert!(!Validators::<T>::get().contains(&who));
+		Ok(())
+	}
+
+	#[benchmark]
+	fn note_author() {
+		let who: T::AccountId = account("validator", 0, 0);
+
+		#[block]
+		{
+			<ValidatorSet<T> as pallet_authorship::EventHandler<_, _>>::note_author(who.clone());
+		}
+
+		assert_eq!(AuthoredBlocks::<T>::get(&who), 1);
+	}
+
+	impl_benchmark_test_suite!(ValidatorSet, crate::mock::new_test_ext(), crate::mock::Test);
+}
@@ -0,0 +1,336 @@
+//! # Validator Set Pallet
+//!
+//! Manages the validators of a proof-of-authority chain as the `SessionManager` of
+//! `pallet_session`. Changes to the set take effect at session boundaries, where the session
+//! pallet rotates the authorities of every consensus pallet in the runtime's session keys, such as
+//! Aura and GRANDPA.
+//!
+//! ## Overview
+//!
+//! - [`Config::AddRemoveOrigin`], e.g. root or a governance origin, changes [`Validators`] with
+//!   [`Pallet::add_validator`] and [`Pallet::remove_validator`].
+//! - A new validator must also register its session keys with `pallet_session`'s `set_keys`
+//!   before it takes part in a session.
+//! - A change made during a session is queued at its end and applies from the session after.
+//! - As the `EventHandler` of `pallet_authorship`, the pallet counts the blocks each validator
+//!   authors in [`AuthoredBlocks`]. A validator that authored no block during a whole session is
+//!   offline and removed at the session's end. Sessions must therefore be long enough to give
+//!   every validator a slot.
+//! - As the `OnOffenceHandler` of `pallet_offences`, e.g. for GRANDPA equivocations, the pallet
+//!   disables an offender in `pallet_session` for the rest of the session and removes it from the
+//!   set.
+//! - The set never shrinks below [`Config::MinAuthorities`], neither by removal nor for being
+//!   offline or an offender.
+//!
+//! The pallet also implements the `SessionManager` of `pallet_session::historical`, which
+//! identifies each validator by its account, so the runtime can prove past session membership.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+
+#[cfg(test)]
+mod tests;
+
+#[cfg(feature = "runtime-benchmarks")]
+mod benchmarking;
+pub mod weights;
+pub use weights::*;
+
+/// The log target of this pallet.
+pub(crate) const LOG_TARGET: &str = "runtime::validator-set";
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use sp_runtime::Perbill;
+	#[cfg(any(feature = "try-runtime", test))]
+	use sp_runtime::TryRuntimeError;
+	use sp_staking::{
+		offence::{DisableStrategy, OffenceDetails, OnOffenceHandler},
+		SessionIndex,
+	};
+	use sp_std::{collections::btree_set::BTreeSet, vec::Vec};
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	/// The pallet's configuration trait.
+	#[pallet::config]
+	pub trait Config:
+		frame_system::Config
+		+ pallet_session::Config<ValidatorId = <Self as frame_system::Config>::AccountId>
+	{
+		/// The overarching runtime event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// A type representing the weights required by the dispatchables of this pallet.
+		type WeightInfo: WeightInfo;
+		/// The origin allowed to add and remove validators.
+		type AddRemoveOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// The fewest validators the set may shrink to.
+		#[pallet::constant]
+		type MinAuthorities: Get<u32>;
+		/// The most validators the set may hold. Must not exceed the `MaxAuthorities` of the
+		/// runtime's consensus pallets.
+		#[pallet::constant]
+		type MaxValidators: Get<u32>;
+	}
+
+	/// The validators of the sessions to come.
+	#[pallet::storage]
+	#[pallet::getter(fn validators)]
+	pub type Validators<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, T::MaxValidators>, ValueQuery>;
+
+	/// The number of blocks each validator authored in the current session.
+	#[pallet::storage]
+	pub type AuthoredBlocks<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+	#[pallet::genesis_config]
+	#[derive(frame_support::DefaultNoBound)]
+	pub struct GenesisConfig<T: Config> {
+		/// The validators of the first sessions. Each must appear at most once and have keys in
+		/// the genesis of `pallet_session`, which must come after this pallet in the runtime.
+		pub initial_validators: Vec<T::AccountId>,
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> BuildGenesisConfig for GenesisConfig<T> {
+		fn build(&self) {
+			let unique = self.initial_validators.iter().collect::<BTreeSet<_>>();
+			assert_eq!(unique.len(), self.initial_validators.len(), "duplicate genesis validators");
+			assert!(
+				self.initial_validators.len() as u32 >= T::MinAuthorities::get(),
+				"fewer genesis validators than `MinAuthorities`"
+			);
+			let validators = BoundedVec::try_from(self.initial_validators.clone())
+				.expect("more genesis validators than `MaxValidators`");
+			Validators::<T>::put(validators);
+		}
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A validator was added to the set.
+		ValidatorAdded {
+			/// The new validator.
+			who: T::AccountId,
+		},
+		/// A validator was removed from the set.
+		ValidatorRemoved {
+			/// The removed validator.
+			who: T::AccountId,
+		},
+		/// A validator authored no block during a session and was removed from the set.
+		OfflineValidatorRemoved {
+			/// The removed validator.
+			who: T::AccountId,
+			/// The session the validator was offline in.
+			session: SessionIndex,
+		},
+		/// A validator committed an offence, was disabled, and removed from the set.
+		OffenderRemoved {
+			/// The removed validator.
+			who: T::AccountId,
+			/// The session of the offence.
+			session: SessionIndex,
+		},
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The account is already a validator.
+		AlreadyValidator,
+		/// The account is not a validator.
+		NotValidator,
+		/// The set would shrink below [`Config::MinAuthorities`].
+		TooFewValidators,
+		/// The set already holds [`Config::MaxValidators`] validators.
+		TooManyValidators,
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_: BlockNumberFor<T>) -> Weight {
+			// `pallet_authorship` notes the block's author without weighing it.
+			<T as Config>::WeightInfo::note_author()
+		}
+
+		fn integrity_test() {
+			assert!(T::MinAuthorities::get() > 0, "`MinAuthorities` must not be zero");
+			assert!(
+				T::MinAuthorities::get() <= T::MaxValidators::get(),
+				"`MinAuthorities` must not exceed `MaxValidators`"
+			);
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn try_state(_: BlockNumberFor<T>) -> Result<(), TryRuntimeError> {
+			Self::do_try_state()
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Add `who` to the validator set.
+		///
+		/// The origin must be [`Config::AddRemoveOrigin`]. `who` joins from the session after the
+		/// current one ends, once it has set its session keys.
+		#[pallet::call_index(0)]
+		#[pallet::weight(<T as Config>::WeightInfo::add_validator())]
+		pub fn add_validator(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			T::AddRemoveOrigin::ensure_origin(origin)?;
+			Validators::<T>::try_mutate(|validators| {
+				ensure!(!validators.contains(&who), Error::<T>::AlreadyValidator);
+				validators.try_push(who.clone()).map_err(|_| Error::<T>::TooManyValidators)
+			})?;
+			Self::deposit_event(Event::ValidatorAdded { who });
+			Ok(())
+		}
+
+		/// Remove `who` from the validator set.
+		///
+		/// The origin must be [`Config::AddRemoveOrigin`]. `who` leaves from the session after the
+		/// current one ends.
+		#[pallet::call_index(1)]
+		#[pallet::weight(<T as Config>::WeightInfo::remove_validator())]
+		pub fn remove_validator(origin: OriginFor<T>, who: T::AccountId) -> DispatchResult {
+			T::AddRemoveOrigin::ensure_origin(origin)?;
+			Self::do_remove_validator(&who)?;
+			Self::deposit_event(Event::ValidatorRemoved { who });
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Remove `who` from [`Validators`], unless that leaves fewer than
+		/// [`Config::MinAuthorities`].
+		fn do_remove_validator(who: &T::AccountId) -> DispatchResult {
+			Validators::<T>::try_mutate(|validators| {
+				let index =
+					validators.iter().position(|v| v == who).ok_or(Error::<T>::NotValidator)?;
+				ensure!(
+					validators.len() as u32 > T::MinAuthorities::get(),
+					Error::<T>::TooFewValidators
+				);
+				validators.remove(index);
+				Ok(())
+			})
+		}
+
+		/// Remove the validators of `session` that authored no block in it, and start counting
+		/// the blocks of the next session.
+		pub(crate) fn remove_offline(session: SessionIndex) {
+			let online = AuthoredBlocks::<T>::drain().map(|(who, _)| who).collect::<BTreeSet<_>>();
+			for who in pallet_session::Pallet::<T>::validators() {
+				if online.contains(&who) {
+					continue
+				}
+				match Self::do_remove_validator(&who) {
+					Ok(()) => Self::deposit_event(Event::OfflineValidatorRemoved { who, session }),
+					// Already removed during the session.
+					Err(e) if e == Error::<T>::NotValidator.into() => {},
+					Err(e) => log::warn!(
+						target: LOG_TARGET,
+						"offline validator {:?} kept in the set: {:?}",
+						who,
+						e
+					),
+				}
+			}
+		}
+
+		/// Check the invariants of the pallet's storage:
+		///
+		/// - no account is in [`Validators`] more than once
+		/// - [`Validators`] holds at least [`Config::MinAuthorities`] accounts
+		#[cfg(any(feature = "try-runtime", test))]
+		pub fn do_try_state() -> Result<(), TryRuntimeError> {
+			let validators = Validators::<T>::get();
+			let unique = validators.iter().collect::<BTreeSet<_>>();
+			ensure!(unique.len() == validators.len(), "a validator is in the set more than once");
+			ensure!(
+				validators.len() as u32 >= T::MinAuthorities::get(),
+				"fewer validators than `MinAuthorities`"
+			);
+			Ok(())
+		}
+	}
+
+	impl<T: Config> pallet_session::SessionManager<T::AccountId> for Pallet<T> {
+		fn new_session(_: SessionIndex) -> Option<Vec<T::AccountId>> {
+			let validators = Validators::<T>::get().into_inner();
+			// Only report actual changes, each of which starts a new GRANDPA authority set.
+			(validators != pallet_session::Pallet::<T>::validators()).then_some(validators)
+		}
+
+		fn end_session(index: SessionIndex) {
+			Self::remove_offline(index);
+		}
+
+		fn start_session(_: SessionIndex) {}
+	}
+
+	/// Identifies validators by their account in historical sessions.
+	impl<T: Config> pallet_session::historical::SessionManager<T::AccountId, T::AccountId>
+		for Pallet<T>
+	{
+		fn new_session(index: SessionIndex) -> Option<Vec<(T::AccountId, T::AccountId)>> {
+			<Self as pallet_session::SessionManager<_>>::new_session(index)
+				.map(|validators| validators.into_iter().map(|v| (v.clone(), v)).collect())
+		}
+
+		fn end_session(index: SessionIndex) {
+			<Self as pallet_session::SessionManager<_>>::end_session(index);
+		}
+
+		fn start_session(index: SessionIndex) {
+			<Self as pallet_session::SessionManager<_>>::start_session(index);
+		}
+	}
+
+	impl<T: Config, Reporter, Identification>
+		OnOffenceHandler<Reporter, (T::AccountId, Identification), Weight> for Pallet<T>
+	{
+		fn on_offence(
+			offenders: &[OffenceDetails<Reporter, (T::AccountId, Identification)>],
+			_slash_fraction: &[Perbill],
+			session: SessionIndex,
+			disable_strategy: DisableStrategy,
+		) -> Weight {
+			for OffenceDetails { offender: (who, _), .. } in offenders {
+				if !matches!(disable_strategy, DisableStrategy::Never) {
+					pallet_session::Pallet::<T>::disable(who);
+				}
+				match Self::do_remove_validator(who) {
+					Ok(()) =>
+						Self::deposit_event(Event::OffenderRemoved { who: who.clone(), session }),
+					Err(e) if e == Error::<T>::NotValidator.into() => {},
+					Err(e) => log::warn!(
+						target: LOG_TARGET,
+						"offender {:?} kept in the set: {:?}",
+						who,
+						e
+					),
+				}
+			}
+			// Disabling reads the session's validators and writes its disabled ones.
+			<T as Config>::WeightInfo::remove_validator()
+				.saturating_add(T::DbWeight::get().reads_writes(2, 1))
+				.saturating_mul(offenders.len() as u64)
+		}
+	}
+
+	impl<T: Config> pallet_authorship::EventHandler<T::AccountId, BlockNumberFor<T>> for Pallet<T> {
+		fn note_author(author: T::AccountId) {
+			AuthoredBlocks::<T>::mutate(author, |blocks| *blocks = blocks.saturating_add(1));
+		}
+	}
+}
@@ -0,0 +1,137 @@
+//! A mock runtime with `pallet_session` managed by the pallet, for its unit tests.
+
+use crate as pallet_validator_set;
+use frame_support::{
+	parameter_types,
+	traits::{ConstU16, ConstU32, ConstU64, Hooks},
+};
+use frame_system::EnsureRoot;
+use pallet_session::{PeriodicSessions, SessionHandler};
+use sp_core::H256;
+use sp_runtime::{
+	key_types::DUMMY,
+	testing::UintAuthorityId,
+	traits::{BlakeTwo256, ConvertInto, IdentityLookup, OpaqueKeys},
+	BuildStorage, KeyTypeId,
+};
+use sp_staking::SessionIndex;
+
+type Block = frame_system::mocking::MockBlock<Test>;
+
+// Configure a mock runtime to test the pallet. The pallet must come before `Session`, whose
+// genesis asks it for the first validators.
+frame_support::construct_runtime!(
+	pub enum Test
+	{
+		System: frame_system,
+		ValidatorSet: pallet_validator_set,
+		Session: pallet_session,
+	}
+);
+
+impl frame_system::Config for Test {
+	type BaseCallFilter = frame_support::traits::Everything;
+	type BlockWeights = ();
+	type BlockLength = ();
+	type DbWeight = ();
+	type RuntimeOrigin = RuntimeOrigin;
+	type RuntimeCall = RuntimeCall;
+	type Nonce = u64;
+	type Hash = H256;
+	type Hashing = BlakeTwo256;
+	type AccountId = u64;
+	type Lookup = IdentityLookup<Self::AccountId>;
+	type Block = Block;
+	type RuntimeEvent = RuntimeEvent;
+	type BlockHashCount = ConstU64<250>;
+	type Version = ();
+	type PalletInfo = PalletInfo;
+	type AccountData = ();
+	type OnNewAccount = ();
+	type OnKilledAccount = ();
+	type SystemWeightInfo = ();
+	type SS58Prefix = ConstU16<42>;
+	type OnSetCode = ();
+	type MaxConsumers = ConstU32<16>;
+}
+
+/// The number of blocks in a session.
+pub const PERIOD: u64 = 5;
+
+parameter_types! {
+	pub const Period: u64 = PERIOD;
+	pub const Offset: u64 = 0;
+}
+
+/// Ignores the session keys, which the pallet does not look at.
+pub struct TestSessionHandler;
+
+impl SessionHandler<u64> for TestSessionHandler {
+	const KEY_TYPE_IDS: &'static [KeyTypeId] = &[DUMMY];
+
+	fn on_genesis_session<Ks: OpaqueKeys>(_: &[(u64, Ks)]) {}
+
+	fn on_new_session<Ks: OpaqueKeys>(_: bool, _: &[(u64, Ks)], _: &[(u64, Ks)]) {}
+
+	fn on_disabled(_: u32) {}
+}
+
+impl pallet_session::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type ValidatorId = u64;
+	type ValidatorIdOf = ConvertInto;
+	type ShouldEndSession = PeriodicSessions<Period, Offset>;
+	type NextSessionRotation = PeriodicSessions<Period, Offset>;
+	type SessionManager = ValidatorSet;
+	type SessionHandler = TestSessionHandler;
+	type Keys = UintAuthorityId;
+	type WeightInfo = ();
+}
+
+impl pallet_validator_set::Config for Test {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = ();
+	type AddRemoveOrigin = EnsureRoot<u64>;
+	type MinAuthorities = ConstU32<2>;
+	type MaxValidators = ConstU32<4>;
+}
+
+/// The validators of the genesis sessions.
+pub const GENESIS_VALIDATORS: [u64; 3] = [1, 2, 3];
+
+// Build genesis storage according to the mock runtime.
+pub fn new_test_ext() -> sp_io::TestExternalities {
+	let mut t = frame_system::GenesisConfig::<Test>::default().build_storage().unwrap();
+	pallet_validator_set::GenesisConfig::<Test> { initial_validators: GENESIS_VALIDATORS.into() }
+		.assimilate_storage(&mut t)
+		.unwrap();
+	pallet_session::GenesisConfig::<Test> {
+		keys: GENESIS_VALIDATORS.into_iter().map(|v| (v, v, UintAuthorityId(v))).collect(),
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	// Events are not recorded in the genesis block.
+	ext.execute_with(|| System::set_block_number(1));
+	ext
+}
+
+/// Register session keys for `who`, giving it the account `set_keys` needs.
+pub fn set_keys(who: u64) {
+	System::inc_providers(&who);
+	Session::set_keys(RuntimeOrigin::signed(who), UintAuthorityId(who), vec![])
+		.expect("the account exists and its keys are new");
+}
+
+/// Author blocks with `authors` in turn until the start of session `index`.
+pub fn run_to_session(index: SessionIndex, authors: &[u64]) {
+	let mut authors = authors.iter().cycle();
+	while Session::current_index() < index {
+		if let Some(author) = authors.next() {
+			<ValidatorSet as pallet_authorship::EventHandler<_, _>>::note_author(*author);
+		}
+		let next = System::block_number() + 1;
+		System::set_block_number(next);
+		Session::on_initialize(next);
+	}
+}
@@ -0,0 +1,175 @@
+use crate::{mock::*, AuthoredBlocks, Error, Event};
+use frame_support::{assert_noop, assert_ok, weights::Weight};
+use pallet_session::SessionManager;
+use sp_runtime::{DispatchError, Perbill};
+use sp_staking::offence::{DisableStrategy, OffenceDetails, OnOffenceHandler};
+
+/// Report an offence by `who` in session 0.
+fn offence(who: u64, disable_strategy: DisableStrategy) {
+	let details = OffenceDetails { offender: (who, who), reporters: vec![] };
+	<ValidatorSet as OnOffenceHandler<u64, (u64, u64), Weight>>::on_offence(
+		&[details],
+		&[Perbill::one()],
+		0,
+		disable_strategy,
+	);
+}
+
+/// The accounts whose keys are queued for the next session.
+fn queued() -> Vec<u64> {
+	Session::queued_keys().into_iter().map(|(who, _)| who).collect()
+}
+
+#[test]
+fn genesis_validators_start_the_first_session() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(ValidatorSet::validators().into_inner(), GENESIS_VALIDATORS);
+		assert_eq!(Session::validators(), GENESIS_VALIDATORS);
+		assert_eq!(queued(), GENESIS_VALIDATORS);
+		assert_ok!(ValidatorSet::do_try_state());
+	});
+}
+
+#[test]
+fn added_validator_joins_after_the_current_session() {
+	new_test_ext().execute_with(|| {
+		set_keys(4);
+		assert_ok!(ValidatorSet::add_validator(RuntimeOrigin::root(), 4));
+		System::assert_last_event(Event::ValidatorAdded { who: 4 }.into());
+		assert_eq!(ValidatorSet::validators().into_inner(), vec![1, 2, 3, 4]);
+
+		run_to_session(1, &GENESIS_VALIDATORS);
+		assert_eq!(Session::validators(), GENESIS_VALIDATORS);
+		assert_eq!(queued(), vec![1, 2, 3, 4]);
+
+		run_to_session(2, &GENESIS_VALIDATORS);
+		assert_eq!(Session::validators(), vec![1, 2, 3, 4]);
+		assert_ok!(ValidatorSet::do_try_state());
+	});
+}
+
+#[test]
+fn removed_validator_leaves_after_the_current_session() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(ValidatorSet::remove_validator(RuntimeOrigin::root(), 3));
+		System::assert_last_event(Event::ValidatorRemoved { who: 3 }.into());
+
+		run_to_session(1, &GENESIS_VALIDATORS);
+		assert_eq!(Session::validators(), GENESIS_VALIDATORS);
+		assert_eq!(queued(), vec![1, 2]);
+
+		// Still authoring until it leaves, but no longer in the set to be removed again.
+		run_to_session(2, &GENESIS_VALIDATORS);
+		assert_eq!(Session::validators(), vec![1, 2]);
+		assert_eq!(ValidatorSet::validators().into_inner(), vec![1, 2]);
+	});
+}
+
+#[test]
+fn only_the_add_remove_origin_changes_validators() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			ValidatorSet::add_validator(RuntimeOrigin::signed(1), 4),
+			DispatchError::BadOrigin
+		);
+		assert_noop!(
+			ValidatorSet::remove_validator(RuntimeOrigin::signed(1), 3),
+			DispatchError::BadOrigin
+		);
+	});
+}
+
+#[test]
+fn validator_set_is_bounded() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			ValidatorSet::add_validator(RuntimeOrigin::root(), 3),
+			Error::<Test>::AlreadyValidator
+		);
+		assert_noop!(
+			ValidatorSet::remove_validator(RuntimeOrigin::root(), 9),
+			Error::<Test>::NotValidator
+		);
+
+		// `MaxValidators` is 4.
+		assert_ok!(ValidatorSet::add_validator(RuntimeOrigin::root(), 4));
+		assert_noop!(
+			ValidatorSet::add_validator(RuntimeOrigin::root(), 5),
+			Error::<Test>::TooManyValidators
+		);
+
+		// `MinAuthorities` is 2.
+		assert_ok!(ValidatorSet::remove_validator(RuntimeOrigin::root(), 4));
+		assert_ok!(ValidatorSet::remove_validator(RuntimeOrigin::root(), 3));
+		assert_noop!(
+			ValidatorSet::remove_validator(RuntimeOrigin::root(), 2),
+			Error::<Test>::TooFewValidators
+		);
+		assert_ok!(ValidatorSet::do_try_state());
+	});
+}
+
+#[test]
+fn offline_validators_are_removed_at_the_end_of_the_session() {
+	new_test_ext().execute_with(|| {
+		run_to_session(1, &[1, 2]);
+		System::assert_has_event(Event::OfflineValidatorRemoved { who: 3, session: 0 }.into());
+		assert_eq!(ValidatorSet::validators().into_inner(), vec![1, 2]);
+		assert_eq!(Session::validators(), GENESIS_VALIDATORS);
+		// Blocks are counted afresh each session.
+		assert_eq!(AuthoredBlocks::<Test>::iter().count(), 0);
+
+		run_to_session(2, &GENESIS_VALIDATORS);
+		assert_eq!(Session::validators(), vec![1, 2]);
+	});
+}
+
+#[test]
+fn offline_validators_are_kept_to_leave_min_authorities() {
+	new_test_ext().execute_with(|| {
+		run_to_session(1, &[1]);
+		System::assert_has_event(Event::OfflineValidatorRemoved { who: 2, session: 0 }.into());
+		assert_eq!(ValidatorSet::validators().into_inner(), vec![1, 3]);
+		assert_ok!(ValidatorSet::do_try_state());
+	});
+}
+
+#[test]
+fn only_changed_validators_are_reported() {
+	new_test_ext().execute_with(|| {
+		run_to_session(1, &GENESIS_VALIDATORS);
+		assert_eq!(ValidatorSet::new_session(2), None);
+
+		assert_ok!(ValidatorSet::add_validator(RuntimeOrigin::root(), 4));
+		assert_eq!(ValidatorSet::new_session(2), Some(vec![1, 2, 3, 4]));
+	});
+}
+
+#[test]
+fn offenders_are_disabled_and_removed() {
+	new_test_ext().execute_with(|| {
+		offence(3, DisableStrategy::WhenSlashed);
+		System::assert_last_event(Event::OffenderRemoved { who: 3, session: 0 }.into());
+		// Disabled by its index in the current session.
+		assert_eq!(Session::disabled_validators(), vec![2]);
+		assert_eq!(ValidatorSet::validators().into_inner(), vec![1, 2]);
+
+		// `MinAuthorities` is 2, so the next offender is only disabled.
+		offence(2, DisableStrategy::Always);
+		assert_eq!(Session::disabled_validators(), vec![1, 2]);
+		assert_eq!(ValidatorSet::validators().into_inner(), vec![1, 2]);
+
+		run_to_session(2, &[1]);
+		assert_eq!(Session::validators(), vec![1, 2]);
+		assert!(Session::disabled_validators().is_empty());
+	});
+}
+
+#[test]
+fn offenders_are_not_disabled_if_the_offence_says_so() {
+	new_test_ext().execute_with(|| {
+		offence(3, DisableStrategy::Never);
+		assert!(Session::disabled_validators().is_empty());
+		assert_eq!(ValidatorSet::validators().into_inner(), vec![1, 2]);
+	});
+}
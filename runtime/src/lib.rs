@@ -6,18 +6,22 @@
 #[cfg(feature = "std")]
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
+use codec::Encode;
 use pallet_grandpa::AuthorityId as GrandpaId;
+use pallet_session::historical as pallet_session_historical;
 use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
 	traits::{
-		AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, One, Verify,
+		AccountIdConversion, AccountIdLookup, BlakeTwo256, Block as BlockT, ConvertInto, Extrinsic as ExtrinsicT,
+		IdentifyAccount, NumberFor, OpaqueKeys, SaturatedConversion, Verify,
 	},
-	transaction_validity::{TransactionSource, TransactionValidity},
-	ApplyExtrinsicResult, MultiSignature,
+	transaction_validity::{TransactionPriority, TransactionSource, TransactionValidity},
+	ApplyExtrinsicResult, FixedPointNumber, MultiSignature,
 };
+use smallvec::smallvec;
 use sp_std::prelude::*;
 #[cfg(feature = "std")]
 use sp_version::NativeVersion;
@@ -27,24 +31,26 @@ use sp_version::RuntimeVersion;
 pub use frame_support::{
 	construct_runtime, parameter_types,
 	traits::{
-		ConstBool, ConstU128, ConstU32, ConstU64, ConstU8, KeyOwnerProofSystem, Randomness,
-		StorageInfo,
+		ConstBool, ConstU128, ConstU32, ConstU64, ConstU8, EitherOfDiverse, EqualPrivilegeOnly,
+		Get, KeyOwnerProofSystem, Randomness, StorageInfo,
 	},
 	weights::{
 		constants::{
 			BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_REF_TIME_PER_SECOND,
 		},
-		IdentityFee, Weight,
+		ConstantMultiplier, IdentityFee, Weight, WeightToFeeCoefficient, WeightToFeeCoefficients,
+		WeightToFeePolynomial,
 	},
-	StorageValue,
+	PalletId, StorageValue,
 };
 pub use frame_system::Call as SystemCall;
+use frame_system::{EnsureRoot, EnsureSigned};
 pub use pallet_balances::Call as BalancesCall;
 pub use pallet_timestamp::Call as TimestampCall;
-use pallet_transaction_payment::{ConstFeeMultiplier, CurrencyAdapter, Multiplier};
+use pallet_transaction_payment::{CurrencyAdapter, Multiplier, TargetedFeeAdjustment};
 #[cfg(any(feature = "std", test))]
 pub use sp_runtime::BuildStorage;
-pub use sp_runtime::{Perbill, Permill};
+pub use sp_runtime::{Perbill, Permill, Perquintill};
 
 /// Import the template pallet.
 pub use pallet_template;
@@ -104,10 +110,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 103,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 	state_version: 1,
 };
 
@@ -204,7 +210,8 @@ impl frame_system::Config for Runtime {
 
 impl pallet_aura::Config for Runtime {
 	type AuthorityId = AuraId;
-	type DisabledValidators = ();
+	/// Validators disabled for an offence author no blocks for the rest of the session.
+	type DisabledValidators = Session;
 	type MaxAuthorities = ConstU32<32>;
 	type AllowMultipleBlocksPerSlot = ConstBool<false>;
 
@@ -212,16 +219,62 @@ impl pallet_aura::Config for Runtime {
 	type SlotDuration = pallet_aura::MinimumPeriodTimesTwo<Runtime>;
 }
 
+parameter_types! {
+	/// GRANDPA keeps the sessions of the authority sets of about the last day, to check the
+	/// ownership proofs of equivocation reports against them.
+	pub const MaxSetIdSessionEntries: u64 = (DAYS / SESSION_PERIOD) as u64;
+	/// Equivocation reports stay valid in the transaction pool for as long as they can be checked.
+	pub const ReportLongevity: u64 = MaxSetIdSessionEntries::get() * SESSION_PERIOD as u64;
+}
+
 impl pallet_grandpa::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 
 	type WeightInfo = ();
 	type MaxAuthorities = ConstU32<32>;
 	type MaxNominators = ConstU32<0>;
-	type MaxSetIdSessionEntries = ConstU64<0>;
+	type MaxSetIdSessionEntries = MaxSetIdSessionEntries;
+
+	type KeyOwnerProof = <Historical as KeyOwnerProofSystem<(KeyTypeId, GrandpaId)>>::Proof;
+	/// Reports equivocations in unsigned transactions, as offences handled by `ValidatorSet`.
+	type EquivocationReportSystem =
+		pallet_grandpa::EquivocationReportSystem<Self, Offences, Historical, ReportLongevity>;
+}
+
+impl pallet_authorship::Config for Runtime {
+	type FindAuthor = pallet_session::FindAccountFromAuthorIndex<Self, Aura>;
+	/// Blocks authored count towards a validator being online.
+	type EventHandler = ValidatorSet;
+}
+
+/// The number of blocks in a session. Changes to the validator set apply at session boundaries.
+pub const SESSION_PERIOD: BlockNumber = 10 * MINUTES;
+
+impl pallet_session::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type ValidatorId = AccountId;
+	type ValidatorIdOf = ConvertInto;
+	type ShouldEndSession = pallet_session::PeriodicSessions<ConstU32<SESSION_PERIOD>, ConstU32<0>>;
+	type NextSessionRotation =
+		pallet_session::PeriodicSessions<ConstU32<SESSION_PERIOD>, ConstU32<0>>;
+	type SessionManager = pallet_session_historical::NoteHistoricalRoot<Self, ValidatorSet>;
+	/// Rotates the authorities of Aura and GRANDPA.
+	type SessionHandler = <opaque::SessionKeys as OpaqueKeys>::KeyTypeIdProviders;
+	type Keys = opaque::SessionKeys;
+	type WeightInfo = pallet_session::weights::SubstrateWeight<Runtime>;
+}
 
-	type KeyOwnerProof = sp_core::Void;
-	type EquivocationReportSystem = ();
+/// Validators are identified by their account in historical sessions.
+impl pallet_session_historical::Config for Runtime {
+	type FullIdentification = AccountId;
+	type FullIdentificationOf = ConvertInto;
+}
+
+/// Offenders are disabled and removed from the validator set.
+impl pallet_offences::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type IdentificationTuple = pallet_session_historical::IdentificationTuple<Self>;
+	type OnOffenceHandler = ValidatorSet;
 }
 
 impl pallet_timestamp::Config for Runtime {
@@ -247,35 +300,281 @@ impl pallet_balances::Config for Runtime {
 	type ExistentialDeposit = ConstU128<EXISTENTIAL_DEPOSIT>;
 	type AccountStore = System;
 	type WeightInfo = pallet_balances::weights::SubstrateWeight<Runtime>;
-	type FreezeIdentifier = ();
-	type MaxFreezes = ();
-	type RuntimeHoldReason = ();
-	type MaxHolds = ();
+	type FreezeIdentifier = RuntimeFreezeReason;
+	type MaxFreezes = ConstU32<2>;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type MaxHolds = ConstU32<6>;
+}
+
+/// What a `transfer_keep_alive` costs for its weight, before the fee multiplier.
+pub const TRANSFER_FEE: Balance = 10 * EXISTENTIAL_DEPOSIT;
+
+/// What a byte of transaction costs, before the fee multiplier.
+pub const TRANSACTION_BYTE_FEE: Balance = EXISTENTIAL_DEPOSIT / 100;
+
+/// Converts weights to fees linearly, calibrated so that the weight of a `transfer_keep_alive`,
+/// extrinsic base weight included, costs [`TRANSFER_FEE`].
+pub struct WeightToFee;
+impl WeightToFeePolynomial for WeightToFee {
+	type Balance = Balance;
+	fn polynomial() -> WeightToFeeCoefficients<Self::Balance> {
+		let transfer = ExtrinsicBaseWeight::get() +
+			<<Runtime as pallet_balances::Config>::WeightInfo as pallet_balances::WeightInfo>::transfer_keep_alive();
+		let p = TRANSFER_FEE;
+		let q = Balance::from(transfer.ref_time());
+		smallvec![WeightToFeeCoefficient {
+			degree: 1,
+			negative: false,
+			coeff_frac: Perbill::from_rational(p % q, q),
+			coeff_integer: p / q,
+		}]
+	}
 }
 
 parameter_types! {
-	pub FeeMultiplier: Multiplier = Multiplier::one();
+	pub const TransactionByteFee: Balance = TRANSACTION_BYTE_FEE;
+	/// Fees go up when blocks are fuller than this, and down when they are emptier.
+	pub const TargetBlockFullness: Perquintill = Perquintill::from_percent(25);
+	/// How fast the fee multiplier follows the block fullness: with full blocks it grows by
+	/// about 0.0056% a block, or about a fifth an hour.
+	pub AdjustmentVariable: Multiplier = Multiplier::saturating_from_rational(75, 1_000_000);
+	/// Fees never go below a tenth of [`WeightToFee`] and [`TransactionByteFee`].
+	pub MinimumMultiplier: Multiplier = Multiplier::saturating_from_rational(1, 10u128);
+	/// Fees never go above ten times [`WeightToFee`] and [`TransactionByteFee`].
+	pub MaximumMultiplier: Multiplier = Multiplier::saturating_from_integer(10);
 }
 
 impl pallet_transaction_payment::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type OnChargeTransaction = CurrencyAdapter<Balances, ()>;
 	type OperationalFeeMultiplier = ConstU8<5>;
-	type WeightToFee = IdentityFee<Balance>;
-	type LengthToFee = IdentityFee<Balance>;
-	type FeeMultiplierUpdate = ConstFeeMultiplier<FeeMultiplier>;
+	type WeightToFee = WeightToFee;
+	type LengthToFee = ConstantMultiplier<Balance, TransactionByteFee>;
+	/// Follows congestion: the multiplier grows while blocks are fuller than
+	/// [`TargetBlockFullness`] and shrinks while they are emptier, within its bounds.
+	type FeeMultiplierUpdate = TargetedFeeAdjustment<
+		Self,
+		TargetBlockFullness,
+		AdjustmentVariable,
+		MinimumMultiplier,
+		MaximumMultiplier,
+	>;
 }
 
+/// Only has a key on development chains, see `chain_spec.rs`. Everywhere else root is reached
+/// through [`Democracy`].
 impl pallet_sudo::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeCall = RuntimeCall;
 	type WeightInfo = pallet_sudo::weights::SubstrateWeight<Runtime>;
 }
 
+parameter_types! {
+	pub MaximumSchedulerWeight: Weight = Perbill::from_percent(80) *
+		BlockWeights::get().max_block;
+}
+
+impl pallet_scheduler::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeOrigin = RuntimeOrigin;
+	type PalletsOrigin = OriginCaller;
+	type RuntimeCall = RuntimeCall;
+	type MaximumWeight = MaximumSchedulerWeight;
+	type ScheduleOrigin = EnsureRoot<AccountId>;
+	type MaxScheduledPerBlock = ConstU32<50>;
+	type WeightInfo = pallet_scheduler::weights::SubstrateWeight<Runtime>;
+	type OriginPrivilegeCmp = EqualPrivilegeOnly;
+	type Preimages = Preimage;
+}
+
+impl pallet_preimage::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = pallet_preimage::weights::SubstrateWeight<Runtime>;
+	type Currency = Balances;
+	type ManagerOrigin = EnsureRoot<AccountId>;
+	type BaseDeposit = ConstU128<{ 10 * EXISTENTIAL_DEPOSIT }>;
+	type ByteDeposit = ConstU128<{ EXISTENTIAL_DEPOSIT / 100 }>;
+}
+
+/// The council: its members are managed by [`CouncilMembership`].
+pub type CouncilCollective = pallet_collective::Instance1;
+
+/// Root, or more than half of the council.
+pub type EnsureRootOrHalfCouncil = EitherOfDiverse<
+	EnsureRoot<AccountId>,
+	pallet_collective::EnsureProportionMoreThan<AccountId, CouncilCollective, 1, 2>,
+>;
+
+parameter_types! {
+	pub MaxCouncilProposalWeight: Weight = Perbill::from_percent(50) *
+		BlockWeights::get().max_block;
+}
+
+impl pallet_collective::Config<CouncilCollective> for Runtime {
+	type RuntimeOrigin = RuntimeOrigin;
+	type Proposal = RuntimeCall;
+	type RuntimeEvent = RuntimeEvent;
+	type MotionDuration = ConstU32<{ 30 * MINUTES }>;
+	type MaxProposals = ConstU32<100>;
+	type MaxMembers = ConstU32<100>;
+	type DefaultVote = pallet_collective::PrimeDefaultVote;
+	type WeightInfo = pallet_collective::weights::SubstrateWeight<Runtime>;
+	/// Members are set by [`CouncilMembership`] instead.
+	type SetMembersOrigin = EnsureRoot<AccountId>;
+	type MaxProposalWeight = MaxCouncilProposalWeight;
+}
+
+impl pallet_membership::Config<pallet_membership::Instance1> for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type AddOrigin = EnsureRootOrHalfCouncil;
+	type RemoveOrigin = EnsureRootOrHalfCouncil;
+	type SwapOrigin = EnsureRootOrHalfCouncil;
+	type ResetOrigin = EnsureRootOrHalfCouncil;
+	type PrimeOrigin = EnsureRootOrHalfCouncil;
+	type MembershipInitialized = Council;
+	type MembershipChanged = Council;
+	type MaxMembers = ConstU32<100>;
+	type WeightInfo = pallet_membership::weights::SubstrateWeight<Runtime>;
+}
+
+/// Referenda dispatch their proposals as root, e.g. `set_code` or the template pallet's admin
+/// calls. Anyone may propose one with a deposit, and the council may table its own.
+impl pallet_democracy::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Currency = Balances;
+	type EnactmentPeriod = ConstU32<HOURS>;
+	type LaunchPeriod = ConstU32<{ 2 * HOURS }>;
+	type VotingPeriod = ConstU32<{ 2 * HOURS }>;
+	type VoteLockingPeriod = ConstU32<HOURS>;
+	type MinimumDeposit = ConstU128<{ 100 * EXISTENTIAL_DEPOSIT }>;
+	/// More than half of the council can table a majority-carries referendum.
+	type ExternalOrigin =
+		pallet_collective::EnsureProportionMoreThan<AccountId, CouncilCollective, 1, 2>;
+	/// Three quarters of the council can table a majority-carries referendum.
+	type ExternalMajorityOrigin =
+		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 3, 4>;
+	/// A unanimous council can table a referendum that passes unless a majority votes against it.
+	type ExternalDefaultOrigin =
+		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 1, 1>;
+	type SubmitOrigin = EnsureSigned<AccountId>;
+	/// Two thirds of the council can fast-track a council referendum.
+	type FastTrackOrigin =
+		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 2, 3>;
+	type InstantOrigin =
+		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 1, 1>;
+	type InstantAllowed = ConstBool<true>;
+	type FastTrackVotingPeriod = ConstU32<{ 10 * MINUTES }>;
+	/// Two thirds of the council can cancel a referendum that has passed.
+	type CancellationOrigin =
+		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 2, 3>;
+	/// A unanimous council can cancel a public proposal.
+	type CancelProposalOrigin = EitherOfDiverse<
+		EnsureRoot<AccountId>,
+		pallet_collective::EnsureProportionAtLeast<AccountId, CouncilCollective, 1, 1>,
+	>;
+	type BlacklistOrigin = EnsureRoot<AccountId>;
+	/// Any council member can veto a council proposal, once, for the cool-off period.
+	type VetoOrigin = pallet_collective::EnsureMember<AccountId, CouncilCollective>;
+	type CooloffPeriod = ConstU32<DAYS>;
+	type Slash = ();
+	type Scheduler = Scheduler;
+	type PalletsOrigin = OriginCaller;
+	type MaxVotes = ConstU32<100>;
+	type WeightInfo = pallet_democracy::weights::SubstrateWeight<Runtime>;
+	type MaxProposals = ConstU32<100>;
+	type Preimages = Preimage;
+	type MaxDeposits = ConstU32<100>;
+	type MaxBlacklisted = ConstU32<100>;
+}
+
+parameter_types! {
+	pub TemplateOracleAccount: AccountId = PalletId(*b"tmpl/orc").into_account_truncating();
+	pub SecondTemplateOracleAccount: AccountId =
+		PalletId(*b"tmpl/or2").into_account_truncating();
+	pub TemplateProposalAccount: AccountId = PalletId(*b"tmpl/gov").into_account_truncating();
+	pub SecondTemplateProposalAccount: AccountId =
+		PalletId(*b"tmpl/go2").into_account_truncating();
+	pub const TemplateApprovalThreshold: Perbill = Perbill::from_percent(50);
+}
+
+/// Validators are added and removed by root or the council, and removed when they go offline.
+impl pallet_validator_set::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = pallet_validator_set::weights::SubstrateWeight<Runtime>;
+	type AddRemoveOrigin = EnsureRootOrHalfCouncil;
+	type MinAuthorities = ConstU32<1>;
+	/// The `MaxAuthorities` of Aura and GRANDPA.
+	type MaxValidators = ConstU32<32>;
+}
+
 /// Configure the pallet-template in pallets/template.
 impl pallet_template::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type WeightInfo = pallet_template::weights::SubstrateWeight<Runtime>;
+	type MaxHistory = ConstU32<100>;
+	type SetOrigin = frame_system::EnsureSigned<AccountId>;
+	type Currency = Balances;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type RuntimeFreezeReason = RuntimeFreezeReason;
+	type ValueDeposit = ConstU128<{ 100 * EXISTENTIAL_DEPOSIT }>;
+	type MaxScheduledPerBlock = ConstU32<50>;
+	type AuthorityId = pallet_template::crypto::TemplateAuthId;
+	type UnsignedInterval = ConstU32<10>;
+	type UnsignedPriority = ConstU64<{ TransactionPriority::MAX / 2 }>;
+	type MaxCounterNameLength = ConstU32<32>;
+	type CounterDeposit = ConstU128<{ 100 * EXISTENTIAL_DEPOSIT }>;
+	type RevealWindow = ConstU32<10>;
+	type MaxExpiringPerBlock = ConstU32<64>;
+	type Time = Timestamp;
+	type MaxFeeders = ConstU32<16>;
+	type RoundLength = ConstU32<MINUTES>;
+	type OracleAccount = TemplateOracleAccount;
+	type ProposalAccount = TemplateProposalAccount;
+	type MaxBatch = ConstU32<64>;
+	type FreeCallsPerEra = ConstU32<10>;
+	type FreeCallEra = ConstU32<HOURS>;
+	type ProposalDeposit = ConstU128<{ 100 * EXISTENTIAL_DEPOSIT }>;
+	type VotingPeriod = ConstU32<{ 10 * MINUTES }>;
+	type ApprovalThreshold = TemplateApprovalThreshold;
+	type MaxProposals = ConstU32<8>;
+	type MaxVoters = ConstU32<64>;
+	type VoteFeeMargin = ConstU128<{ 10 * TRANSFER_FEE }>;
+}
+
+/// A second template registry, only writable by accounts root has allowed.
+///
+/// Instances share the weights benchmarked for the default one.
+impl pallet_template::Config<pallet_template::Instance2> for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type WeightInfo = pallet_template::weights::SubstrateWeight<Runtime>;
+	type MaxHistory = ConstU32<20>;
+	type SetOrigin = pallet_template::EnsureWriter<Runtime, pallet_template::Instance2>;
+	type Currency = Balances;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type RuntimeFreezeReason = RuntimeFreezeReason;
+	type ValueDeposit = ConstU128<{ 10 * EXISTENTIAL_DEPOSIT }>;
+	type MaxScheduledPerBlock = ConstU32<10>;
+	type AuthorityId = pallet_template::crypto::TemplateAuthId;
+	type UnsignedInterval = ConstU32<10>;
+	type UnsignedPriority = ConstU64<{ TransactionPriority::MAX / 2 }>;
+	type MaxCounterNameLength = ConstU32<32>;
+	type CounterDeposit = ConstU128<{ 10 * EXISTENTIAL_DEPOSIT }>;
+	type RevealWindow = ConstU32<10>;
+	type MaxExpiringPerBlock = ConstU32<64>;
+	type Time = Timestamp;
+	type MaxFeeders = ConstU32<16>;
+	type RoundLength = ConstU32<MINUTES>;
+	type OracleAccount = SecondTemplateOracleAccount;
+	type ProposalAccount = SecondTemplateProposalAccount;
+	type MaxBatch = ConstU32<16>;
+	type FreeCallsPerEra = ConstU32<0>;
+	type FreeCallEra = ConstU32<HOURS>;
+	type ProposalDeposit = ConstU128<{ 10 * EXISTENTIAL_DEPOSIT }>;
+	type VotingPeriod = ConstU32<{ 10 * MINUTES }>;
+	type ApprovalThreshold = TemplateApprovalThreshold;
+	type MaxProposals = ConstU32<4>;
+	type MaxVoters = ConstU32<32>;
+	type VoteFeeMargin = ConstU128<{ 10 * TRANSFER_FEE }>;
 }
 
 // Create the runtime by composing the FRAME pallets that were previously configured.
@@ -290,6 +589,21 @@ construct_runtime!(
 		Sudo: pallet_sudo,
 		// Include the custom logic from the pallet-template in the runtime.
 		TemplateModule: pallet_template,
+		// A pallet's index is part of the encoding of its calls and of its hold and freeze reasons
+		// stored on-chain, so new pallets go after the existing ones.
+		Scheduler: pallet_scheduler,
+		Preimage: pallet_preimage,
+		Council: pallet_collective::<Instance1>,
+		CouncilMembership: pallet_membership::<Instance1>,
+		Democracy: pallet_democracy,
+		SecondTemplate: pallet_template::<Instance2>,
+		// `Authorship` must find the block's author before `Session` rotates the authorities, and
+		// `Session`'s genesis takes its validators from `ValidatorSet`.
+		Authorship: pallet_authorship,
+		ValidatorSet: pallet_validator_set,
+		Session: pallet_session,
+		Historical: pallet_session_historical::{Pallet},
+		Offences: pallet_offences,
 	}
 );
 
@@ -308,7 +622,10 @@ pub type SignedExtra = (
 	frame_system::CheckEra<Runtime>,
 	frame_system::CheckNonce<Runtime>,
 	frame_system::CheckWeight<Runtime>,
-	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	pallet_template::FreeCallQuota<
+		Runtime,
+		pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	>,
 );
 
 /// Unchecked extrinsic type as expected by this runtime.
@@ -316,6 +633,84 @@ pub type UncheckedExtrinsic =
 	generic::UncheckedExtrinsic<Address, RuntimeCall, Signature, SignedExtra>;
 /// The payload being signed in transactions.
 pub type SignedPayload = generic::SignedPayload<RuntimeCall, SignedExtra>;
+
+impl frame_system::offchain::SigningTypes for Runtime {
+	type Public = <Signature as Verify>::Signer;
+	type Signature = Signature;
+}
+
+impl<C> frame_system::offchain::SendTransactionTypes<C> for Runtime
+where
+	RuntimeCall: From<C>,
+{
+	type OverarchingCall = RuntimeCall;
+	type Extrinsic = UncheckedExtrinsic;
+}
+
+/// Lets offchain workers sign transactions with keys from the node's keystore.
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Runtime
+where
+	RuntimeCall: From<LocalCall>,
+{
+	fn create_transaction<C: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+		call: RuntimeCall,
+		public: <Signature as Verify>::Signer,
+		account: AccountId,
+		nonce: Nonce,
+	) -> Option<(RuntimeCall, <UncheckedExtrinsic as ExtrinsicT>::SignaturePayload)> {
+		// Keep the transaction valid for half of the blocks whose hashes are kept.
+		let period =
+			BlockHashCount::get().checked_next_power_of_two().map(|c| c / 2).unwrap_or(2) as u64;
+		let current_block = System::block_number().saturated_into::<u64>().saturating_sub(1);
+		let extra: SignedExtra = (
+			frame_system::CheckNonZeroSender::<Runtime>::new(),
+			frame_system::CheckSpecVersion::<Runtime>::new(),
+			frame_system::CheckTxVersion::<Runtime>::new(),
+			frame_system::CheckGenesis::<Runtime>::new(),
+			frame_system::CheckEra::<Runtime>::from(generic::Era::mortal(period, current_block)),
+			frame_system::CheckNonce::<Runtime>::from(nonce),
+			frame_system::CheckWeight::<Runtime>::new(),
+			pallet_template::FreeCallQuota::from(
+				pallet_transaction_payment::ChargeTransactionPayment::<Runtime>::from(0),
+			),
+		);
+		let raw_payload = SignedPayload::new(call, extra).ok()?;
+		let signature = raw_payload.using_encoded(|payload| C::sign(payload, public))?;
+		let (call, extra, _) = raw_payload.deconstruct();
+		Some((call, (sp_runtime::MultiAddress::Id(account), signature, extra)))
+	}
+}
+
+/// The current sudo key, which takes ownership of the template pallet's old global value.
+pub struct SudoKey;
+impl Get<Option<AccountId>> for SudoKey {
+	fn get() -> Option<AccountId> {
+		Sudo::key()
+	}
+}
+
+/// Migrations to apply on runtime upgrade.
+///
+/// Each entry is a no-op once applied: the pallets' own migrations and
+/// [`migrations::InitStorageVersion`] check the on-chain storage version, and
+/// [`migrations::SeedValidatorSet`] whether there are validators already.
+pub type Migrations = (
+	pallet_template::migrations::v1::MigrateToPerAccount<Runtime, SudoKey>,
+	// The pallets added since genesis.
+	migrations::InitStorageVersion<Scheduler>,
+	migrations::InitStorageVersion<Preimage>,
+	migrations::InitStorageVersion<Council>,
+	migrations::InitStorageVersion<CouncilMembership>,
+	migrations::InitStorageVersion<Democracy>,
+	migrations::InitStorageVersion<SecondTemplate>,
+	migrations::InitStorageVersion<Authorship>,
+	migrations::InitStorageVersion<ValidatorSet>,
+	migrations::InitStorageVersion<Session>,
+	migrations::InitStorageVersion<Historical>,
+	migrations::InitStorageVersion<Offences>,
+	migrations::SeedValidatorSet,
+);
+
 /// Executive: handles dispatch to the various modules.
 pub type Executive = frame_executive::Executive<
 	Runtime,
@@ -323,8 +718,14 @@ pub type Executive = frame_executive::Executive<
 	frame_system::ChainContext<Runtime>,
 	Runtime,
 	AllPalletsWithSystem,
+	Migrations,
 >;
 
+pub mod migrations;
+
+#[cfg(test)]
+mod tests;
+
 #[cfg(feature = "runtime-benchmarks")]
 #[macro_use]
 extern crate frame_benchmarking;
@@ -337,7 +738,13 @@ mod benches {
 		[pallet_balances, Balances]
 		[pallet_timestamp, Timestamp]
 		[pallet_sudo, Sudo]
+		[pallet_scheduler, Scheduler]
+		[pallet_preimage, Preimage]
+		[pallet_collective, Council]
+		[pallet_membership, CouncilMembership]
+		[pallet_democracy, Democracy]
 		[pallet_template, TemplateModule]
+		[pallet_validator_set, ValidatorSet]
 	);
 }
 
@@ -439,23 +846,26 @@ impl_runtime_apis! {
 		}
 
 		fn submit_report_equivocation_unsigned_extrinsic(
-			_equivocation_proof: sp_consensus_grandpa::EquivocationProof<
+			equivocation_proof: sp_consensus_grandpa::EquivocationProof<
 				<Block as BlockT>::Hash,
 				NumberFor<Block>,
 			>,
-			_key_owner_proof: sp_consensus_grandpa::OpaqueKeyOwnershipProof,
+			key_owner_proof: sp_consensus_grandpa::OpaqueKeyOwnershipProof,
 		) -> Option<()> {
-			None
+			let key_owner_proof = key_owner_proof.decode()?;
+
+			Grandpa::submit_unsigned_equivocation_report(equivocation_proof, key_owner_proof)
 		}
 
 		fn generate_key_ownership_proof(
 			_set_id: sp_consensus_grandpa::SetId,
-			_authority_id: GrandpaId,
+			authority_id: GrandpaId,
 		) -> Option<sp_consensus_grandpa::OpaqueKeyOwnershipProof> {
-			// NOTE: this is the only implementation possible since we've
-			// defined our key owner proof type as a bottom type (i.e. a type
-			// with no values).
-			None
+			// The proof is of the current session, whatever the set: the client only asks for
+			// proofs of the sets it is voting in.
+			Historical::prove((sp_consensus_grandpa::KEY_TYPE, authority_id))
+				.map(|proof| proof.encode())
+				.map(sp_consensus_grandpa::OpaqueKeyOwnershipProof::new)
 		}
 	}
 
@@ -509,6 +919,30 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_template_runtime_api::TemplateApi<Block, AccountId, BlockNumber> for Runtime {
+		fn latest() -> Option<pallet_template::HistoryEntry<BlockNumber, AccountId>> {
+			TemplateModule::latest()
+		}
+		fn history(limit: u32) -> Vec<pallet_template::HistoryEntry<BlockNumber, AccountId>> {
+			TemplateModule::history(limit)
+		}
+		fn value_at(block: BlockNumber) -> Option<pallet_template::HistoryEntry<BlockNumber, AccountId>> {
+			TemplateModule::value_at(block)
+		}
+	}
+
+	impl pallet_template_runtime_api::TemplateOracleApi<Block, u64> for Runtime {
+		fn aggregated() -> Option<(u32, u64)> {
+			TemplateModule::aggregated()
+		}
+	}
+
+	impl pallet_template_runtime_api::TemplateProofApi<Block, AccountId> for Runtime {
+		fn value_key(who: AccountId) -> Vec<u8> {
+			TemplateModule::value_key(&who)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
@@ -0,0 +1,169 @@
+//! Runtime-level storage migrations, for changes spanning several pallets.
+
+use crate::*;
+#[cfg(feature = "try-runtime")]
+use codec::Decode;
+use frame_support::{
+	storage::{with_transaction, TransactionOutcome},
+	traits::{GetStorageVersion, OnRuntimeUpgrade, PalletInfoAccess, StorageVersion},
+	BoundedVec,
+};
+use pallet_session::WeightInfo as _;
+#[cfg(feature = "try-runtime")]
+use sp_runtime::TryRuntimeError;
+use sp_std::marker::PhantomData;
+
+/// The log target of the runtime's migrations.
+const LOG_TARGET: &str = "runtime::migrations";
+
+/// Writes the in-code storage version of pallet `P`, which was added to the runtime after genesis
+/// and so never had one written.
+///
+/// A pallet's genesis writes its storage version, but adding a pallet in an upgrade runs no
+/// genesis, which would leave it at version 0 and make its later versioned migrations skip it.
+/// Only writes while the on-chain version is still 0, so it's a no-op once applied. Must not be
+/// used for a pallet that has storage from before, which its own migrations have to upgrade.
+pub struct InitStorageVersion<P>(PhantomData<P>);
+
+impl<P: GetStorageVersion + PalletInfoAccess> OnRuntimeUpgrade for InitStorageVersion<P> {
+	fn on_runtime_upgrade() -> Weight {
+		let db_weight = <Runtime as frame_system::Config>::DbWeight::get();
+		let current = P::current_storage_version();
+		if P::on_chain_storage_version() != StorageVersion::new(0) || current == 0 {
+			return db_weight.reads(1)
+		}
+		current.put::<P>();
+		db_weight.reads_writes(1, 1)
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(_state: Vec<u8>) -> Result<(), TryRuntimeError> {
+		frame_support::ensure!(
+			P::on_chain_storage_version() == P::current_storage_version(),
+			"init storage version: on-chain storage version differs from the pallet's"
+		);
+		Ok(())
+	}
+}
+
+/// Seeds `ValidatorSet` and `Session` from the Aura and GRANDPA authorities of a chain that ran
+/// without them.
+///
+/// Without it, the first session rotation after adding the two pallets would hand Aura and GRANDPA
+/// an empty set of authorities and halt the chain. Each authority's account is the one of its Aura
+/// key, as in the chain specs, and its session keys are its current Aura and GRANDPA keys. The
+/// accounts are created if they don't exist, like `Session`'s genesis does.
+///
+/// Does nothing once `ValidatorSet` has validators. Seeds nothing either, and logs an error, if
+/// Aura and GRANDPA don't have the same number of authorities, as then they can't be paired, if
+/// there are more than `MaxValidators`, or if the session keys of any authority can't be set.
+pub struct SeedValidatorSet;
+
+impl SeedValidatorSet {
+	/// The accounts and session keys of the current authorities, if they can be paired.
+	fn authorities() -> Option<Vec<(AccountId, opaque::SessionKeys)>> {
+		let aura = Aura::authorities();
+		let grandpa = Grandpa::grandpa_authorities();
+		if aura.len() != grandpa.len() {
+			return None
+		}
+		let authorities = aura
+			.into_iter()
+			.zip(grandpa)
+			.map(|(aura, (grandpa, _))| {
+				let public: &sp_core::sr25519::Public = aura.as_ref();
+				let account = AccountId::from(*public);
+				(account, opaque::SessionKeys { aura, grandpa })
+			})
+			.collect();
+		Some(authorities)
+	}
+}
+
+impl OnRuntimeUpgrade for SeedValidatorSet {
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+		let seeds = ValidatorSet::validators().is_empty();
+		Ok((seeds, Aura::authorities().into_inner()).encode())
+	}
+
+	fn on_runtime_upgrade() -> Weight {
+		let db_weight = <Runtime as frame_system::Config>::DbWeight::get();
+		let checked = db_weight.reads(3);
+		if !ValidatorSet::validators().is_empty() {
+			return checked
+		}
+		let Some(authorities) = Self::authorities() else {
+			log::error!(
+				target: LOG_TARGET,
+				"not seeding the validator set: Aura and GRANDPA have different authorities"
+			);
+			return checked
+		};
+		let max = <Runtime as pallet_validator_set::Config>::MaxValidators::get();
+		if authorities.len() > max as usize {
+			log::error!(
+				target: LOG_TARGET,
+				"not seeding the validator set: {} authorities, but at most {} validators",
+				authorities.len(),
+				max
+			);
+			return checked
+		}
+
+		let weight = checked.saturating_add(
+			<Runtime as pallet_session::Config>::WeightInfo::set_keys()
+				.saturating_add(db_weight.writes(1))
+				.saturating_mul(authorities.len() as u64),
+		);
+		// Seed every authority or none, as leaving any out could halt the chain.
+		let seeded = with_transaction(|| {
+			for (account, keys) in &authorities {
+				if !System::can_inc_consumer(account) {
+					System::inc_providers(account);
+				}
+				let origin = RuntimeOrigin::signed(account.clone());
+				if let Err(e) = Session::set_keys(origin, keys.clone(), Vec::new()) {
+					log::error!(
+						target: LOG_TARGET,
+						"not seeding the validator set: failed to set the session keys of {:?}: {:?}",
+						account,
+						e
+					);
+					return TransactionOutcome::Rollback(Err(e))
+				}
+			}
+			TransactionOutcome::Commit(Ok(()))
+		});
+		if seeded.is_err() {
+			return weight
+		}
+
+		// The current session runs with the authorities, and so does the next one.
+		let validators = authorities.iter().map(|(account, _)| account.clone()).collect::<Vec<_>>();
+		pallet_session::Validators::<Runtime>::put(&validators);
+		pallet_session::QueuedKeys::<Runtime>::put(authorities);
+		pallet_validator_set::Validators::<Runtime>::put(BoundedVec::truncate_from(validators));
+		log::info!(target: LOG_TARGET, "seeded the validator set from the authorities");
+		weight.saturating_add(db_weight.writes(3))
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+		let (seeds, authorities) = <(bool, Vec<AuraId>)>::decode(&mut &state[..])
+			.map_err(|_| "seed validator set: failed to decode pre-upgrade state")?;
+		if seeds {
+			frame_support::ensure!(
+				ValidatorSet::validators().len() == authorities.len(),
+				"seed validator set: not every authority became a validator"
+			);
+		}
+		let queued =
+			Session::queued_keys().into_iter().map(|(_, keys)| keys.aura).collect::<Vec<_>>();
+		frame_support::ensure!(
+			queued == authorities,
+			"seed validator set: the next session would change the Aura authorities"
+		);
+		Ok(())
+	}
+}
@@ -0,0 +1,245 @@
+use crate::*;
+use frame_support::{
+	assert_ok,
+	dispatch::DispatchClass,
+	traits::{fungible::Mutate, GetStorageVersion, OnRuntimeUpgrade, StorageVersion},
+	weights::{WeightToFee as _, WeightToFeePolynomial as _},
+	BoundedVec,
+};
+use pallet_template::FREE_QUOTA_EXHAUSTED;
+use sp_core::{ed25519, sr25519, Pair};
+use sp_runtime::{
+	traits::Convert,
+	transaction_validity::{InvalidTransaction, TransactionSource, TransactionValidity},
+};
+use std::cmp::Ordering;
+
+type FeeMultiplierUpdate = <Runtime as pallet_transaction_payment::Config>::FeeMultiplierUpdate;
+
+fn new_test_ext() -> sp_io::TestExternalities {
+	frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap().into()
+}
+
+/// The multiplier after a block with `fullness` of the normal dispatch weight used.
+fn next_multiplier(fullness: Perbill, previous: Multiplier) -> Multiplier {
+	let max_normal = BlockWeights::get().get(DispatchClass::Normal).max_total.unwrap();
+	System::set_block_consumed_resources(fullness * max_normal, 0);
+	FeeMultiplierUpdate::convert(previous)
+}
+
+/// Runs blocks with `fullness` until the multiplier stops changing, checking that it moves in
+/// `direction` and stays within its bounds, and returns it with the number of blocks it took.
+fn run_until_converged(
+	fullness: Perbill,
+	direction: Ordering,
+	mut multiplier: Multiplier,
+) -> (Multiplier, u32) {
+	for blocks in 1..=7 * DAYS {
+		let next = next_multiplier(fullness, multiplier);
+		assert!(next >= MinimumMultiplier::get() && next <= MaximumMultiplier::get());
+		if next == multiplier {
+			return (next, blocks)
+		}
+		assert_eq!(next.cmp(&multiplier), direction);
+		multiplier = next;
+	}
+	panic!("the multiplier did not converge within a week of blocks");
+}
+
+#[test]
+fn weight_to_fee_is_calibrated_on_transfers() {
+	let transfer = ExtrinsicBaseWeight::get() +
+		<<Runtime as pallet_balances::Config>::WeightInfo as pallet_balances::WeightInfo>::transfer_keep_alive();
+
+	assert!(WeightToFee::weight_to_fee(&transfer).abs_diff(TRANSFER_FEE) <= 1);
+	// Fees are linear in weight.
+	assert!(WeightToFee::weight_to_fee(&(transfer * 10)).abs_diff(10 * TRANSFER_FEE) <= 10);
+	assert_eq!(WeightToFee::polynomial().len(), 1);
+}
+
+#[test]
+fn length_to_fee_is_by_byte() {
+	type LengthToFee = <Runtime as pallet_transaction_payment::Config>::LengthToFee;
+
+	assert_eq!(LengthToFee::weight_to_fee(&Weight::from_parts(100, 0)), 100 * TRANSACTION_BYTE_FEE);
+}
+
+#[test]
+fn multiplier_converges_to_its_maximum_with_full_blocks() {
+	new_test_ext().execute_with(|| {
+		let (multiplier, blocks) = run_until_converged(
+			Perbill::one(),
+			Ordering::Greater,
+			Multiplier::saturating_from_integer(1),
+		);
+
+		assert_eq!(multiplier, MaximumMultiplier::get());
+		// From 1 to 10 at about 0.0056% a block.
+		assert!((40_000..42_000).contains(&blocks), "took {blocks} blocks");
+		// And stays there.
+		for _ in 0..100 {
+			assert_eq!(next_multiplier(Perbill::one(), multiplier), multiplier);
+		}
+	});
+}
+
+#[test]
+fn multiplier_converges_to_its_minimum_with_empty_blocks() {
+	new_test_ext().execute_with(|| {
+		let (multiplier, _) =
+			run_until_converged(Perbill::zero(), Ordering::Less, MaximumMultiplier::get());
+
+		assert_eq!(multiplier, MinimumMultiplier::get());
+		assert_eq!(next_multiplier(Perbill::zero(), multiplier), multiplier);
+	});
+}
+
+/// `call` signed by `signer` with `nonce`, carrying the runtime's signed extensions.
+fn signed_extrinsic(signer: &sr25519::Pair, nonce: Nonce, call: RuntimeCall) -> UncheckedExtrinsic {
+	let extra: SignedExtra = (
+		frame_system::CheckNonZeroSender::<Runtime>::new(),
+		frame_system::CheckSpecVersion::<Runtime>::new(),
+		frame_system::CheckTxVersion::<Runtime>::new(),
+		frame_system::CheckGenesis::<Runtime>::new(),
+		frame_system::CheckEra::<Runtime>::from(generic::Era::Immortal),
+		frame_system::CheckNonce::<Runtime>::from(nonce),
+		frame_system::CheckWeight::<Runtime>::new(),
+		pallet_template::FreeCallQuota::from(
+			pallet_transaction_payment::ChargeTransactionPayment::<Runtime>::from(0),
+		),
+	);
+	let payload = SignedPayload::new(call, extra).unwrap();
+	let signature = payload.using_encoded(|payload| signer.sign(payload));
+	let (call, extra, _) = payload.deconstruct();
+	UncheckedExtrinsic::new_signed(
+		call,
+		Address::Id(signer.public().into()),
+		signature.into(),
+		extra,
+	)
+}
+
+/// Validate `xt` the way the transaction pool does before admitting it.
+fn validate(xt: UncheckedExtrinsic) -> TransactionValidity {
+	Executive::validate_transaction(TransactionSource::External, xt, System::block_hash(0))
+}
+
+#[test]
+fn pool_admits_one_pending_free_call_per_slot() {
+	new_test_ext().execute_with(|| {
+		let signer = sr25519::Pair::from_seed(&[1; 32]);
+		let who = AccountId::from(signer.public());
+		// A value for the first call to clear, and then just enough to exist, not to pay any fee.
+		let deposit = <Runtime as pallet_template::Config>::ValueDeposit::get();
+		Balances::set_balance(&who, EXISTENTIAL_DEPOSIT + deposit);
+		assert_ok!(TemplateModule::do_something(RuntimeOrigin::signed(who.clone()), 1));
+		let call = || RuntimeCall::TemplateModule(pallet_template::Call::clear_something {});
+
+		// Two pending free calls validated against the same count claim the same slot, so the
+		// pool keeps only one of them.
+		let first = validate(signed_extrinsic(&signer, 0, call())).unwrap();
+		let second = validate(signed_extrinsic(&signer, 1, call())).unwrap();
+		assert!(first.provides.iter().any(|tag| second.provides.contains(tag)));
+
+		// Once the first is included the next call takes the next slot.
+		assert!(Executive::apply_extrinsic(signed_extrinsic(&signer, 0, call())).is_ok());
+		let next = validate(signed_extrinsic(&signer, 1, call())).unwrap();
+		assert!(!first.provides.iter().any(|tag| next.provides.contains(tag)));
+
+		// With the quota used up, calls the sender cannot pay for are turned away.
+		Balances::set_balance(&who, EXISTENTIAL_DEPOSIT);
+		let era = System::block_number() / <Runtime as pallet_template::Config>::FreeCallEra::get();
+		let quota = <Runtime as pallet_template::Config>::FreeCallsPerEra::get();
+		pallet_template::FreeCallsUsed::<Runtime>::insert(&who, (era, quota));
+		assert_eq!(
+			validate(signed_extrinsic(&signer, 1, call())),
+			InvalidTransaction::Custom(FREE_QUOTA_EXHAUSTED).into()
+		);
+	});
+}
+
+fn aura(seed: u8) -> AuraId {
+	AuraId::from(sr25519::Pair::from_seed(&[seed; 32]).public())
+}
+
+fn grandpa(seed: u8) -> GrandpaId {
+	GrandpaId::from(ed25519::Pair::from_seed(&[seed; 32]).public())
+}
+
+/// A chain without validator set or session keys, whose Aura and GRANDPA authorities have the
+/// keys of the given seeds.
+fn authorities_ext(aura_seeds: &[u8], grandpa_seeds: &[u8]) -> sp_io::TestExternalities {
+	let mut storage = frame_system::GenesisConfig::<Runtime>::default().build_storage().unwrap();
+	pallet_aura::GenesisConfig::<Runtime> {
+		authorities: aura_seeds.iter().copied().map(aura).collect(),
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+	pallet_grandpa::GenesisConfig::<Runtime> {
+		authorities: grandpa_seeds.iter().map(|seed| (grandpa(*seed), 1)).collect(),
+		..Default::default()
+	}
+	.assimilate_storage(&mut storage)
+	.unwrap();
+	storage.into()
+}
+
+#[test]
+fn validator_set_is_seeded_from_the_authorities() {
+	authorities_ext(&[1, 2], &[1, 2]).execute_with(|| {
+		migrations::SeedValidatorSet::on_runtime_upgrade();
+
+		let accounts = [1, 2]
+			.map(|seed| AccountId::from(sr25519::Pair::from_seed(&[seed; 32]).public()))
+			.to_vec();
+		assert_eq!(ValidatorSet::validators().into_inner(), accounts);
+		assert_eq!(Session::validators(), accounts);
+		let keys =
+			[1, 2].map(|seed| opaque::SessionKeys { aura: aura(seed), grandpa: grandpa(seed) });
+		assert_eq!(Session::queued_keys(), accounts.iter().cloned().zip(keys).collect::<Vec<_>>());
+
+		// The next session keeps the authorities instead of clearing them.
+		Session::rotate_session();
+		assert_eq!(Aura::authorities().into_inner(), vec![aura(1), aura(2)]);
+		assert_eq!(Grandpa::grandpa_authorities(), vec![(grandpa(1), 1), (grandpa(2), 1)]);
+
+		// Once there are validators, it leaves them alone.
+		let validators = BoundedVec::truncate_from(accounts[..1].to_vec());
+		pallet_validator_set::Validators::<Runtime>::put(validators.clone());
+		migrations::SeedValidatorSet::on_runtime_upgrade();
+		assert_eq!(ValidatorSet::validators(), validators);
+	});
+}
+
+#[test]
+fn pallets_added_after_genesis_get_their_storage_version() {
+	new_test_ext().execute_with(|| {
+		assert_eq!(SecondTemplate::on_chain_storage_version(), 0);
+		migrations::InitStorageVersion::<SecondTemplate>::on_runtime_upgrade();
+		assert_eq!(
+			SecondTemplate::on_chain_storage_version(),
+			SecondTemplate::current_storage_version()
+		);
+
+		// A pallet already past version 0 is left to its own migrations.
+		StorageVersion::new(7).put::<SecondTemplate>();
+		migrations::InitStorageVersion::<SecondTemplate>::on_runtime_upgrade();
+		assert_eq!(SecondTemplate::on_chain_storage_version(), 7);
+	});
+}
+
+#[test]
+fn validator_set_is_not_seeded_unless_every_authority_can_be() {
+	// Both authorities share a GRANDPA key, which only one of them can register.
+	authorities_ext(&[1, 2], &[1, 1]).execute_with(|| {
+		migrations::SeedValidatorSet::on_runtime_upgrade();
+
+		assert!(ValidatorSet::validators().is_empty());
+		assert!(Session::validators().is_empty());
+		assert!(Session::queued_keys().is_empty());
+		// Nothing of the first authority's keys or account is left behind.
+		let first = AccountId::from(sr25519::Pair::from_seed(&[1; 32]).public());
+		assert_eq!(Session::key_owner(sp_core::crypto::key_types::AURA, aura(1).as_ref()), None);
+		assert!(!System::account_exists(&first));
+	});
+}
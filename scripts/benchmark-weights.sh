@@ -0,0 +1,27 @@
+#!/usr/bin/env bash
+# Benchmark pallet_template on a dev chain and regenerate pallets/template/src/weights.rs.
+#
+# STEPS and REPEAT override the number of steps per component and repetitions per step.
+set -e
+
+cd "$(dirname "$0")/.."
+
+STEPS=${STEPS:-50}
+REPEAT=${REPEAT:-20}
+
+echo "*** Checking the benchmarks against the pallet's mock runtime"
+cargo test -p pallet-template --features runtime-benchmarks benchmark
+
+echo "*** Building the node with runtime benchmarks"
+cargo build --release -p node-template --features runtime-benchmarks
+
+echo "*** Benchmarking pallet_template"
+./target/release/node-template benchmark pallet \
+	--chain dev \
+	--pallet pallet_template \
+	--extrinsic '*' \
+	--steps "$STEPS" \
+	--repeat "$REPEAT" \
+	--wasm-execution compiled \
+	--output pallets/template/src/weights.rs \
+	--template .maintain/frame-weight-template.hbs